@@ -0,0 +1,33 @@
+//! Helpers shared by the benchmarks.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// A deterministic day4-style roll layout, roughly 60% full.
+pub fn rolls(size: usize) -> Vec<Vec<bool>> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state % 10 < 6
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Runs `f` repeatedly for about half a second and returns the fastest run.
+pub fn bench<T>(mut f: impl FnMut() -> T) -> Duration {
+    let mut best = Duration::MAX;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(500) {
+        let run = Instant::now();
+        black_box(f());
+        best = best.min(run.elapsed());
+    }
+    best
+}
@@ -0,0 +1,38 @@
+//! Compares removing day4 rolls a generation at a time, rescanning every roll
+//! each round, against the worklist in `day4::remove_all`. Run with
+//! `cargo bench --bench day4`.
+
+mod common;
+
+use aoc2025::problems::day4;
+use aoc2025::{Grid, SparseGrid};
+use common::{bench, rolls};
+
+fn generations(grid: &Grid<bool>) -> usize {
+    let mut rolls = SparseGrid::from_grid(grid, |&roll| roll);
+    let mut removed = 0;
+    loop {
+        let wave = day4::clean_up_rolls(&mut rolls);
+        if wave == 0 {
+            return removed;
+        }
+        removed += wave;
+    }
+}
+
+fn main() {
+    for size in [137, 500] {
+        let grid = Grid::new(rolls(size));
+        assert_eq!(generations(&grid), day4::remove_all(&grid));
+
+        let scans = bench(|| generations(&grid));
+        let worklist = bench(|| day4::remove_all(&grid));
+        println!(
+            "{0}x{0} grid: generations {1:>12.2?}   worklist {2:>12.2?}   {3:>6.2}x",
+            size,
+            scans,
+            worklist,
+            scans.as_secs_f64() / worklist.as_secs_f64()
+        );
+    }
+}
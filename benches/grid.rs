@@ -0,0 +1,141 @@
+//! Compares the flat `Grid` against the nested `Vec<Vec<Cell>>` layout it
+//! replaced. Run with `cargo bench --bench grid`.
+
+mod common;
+
+use std::mem::size_of;
+use std::time::Duration;
+
+use aoc2025::{GRID_DIRECTIONS, Grid, Position};
+use common::{bench, rolls};
+
+/// The previous grid layout, where every cell stores its own position.
+struct NestedCell<T> {
+    value: T,
+    position: Position,
+}
+
+struct NestedGrid<T> {
+    cells: Vec<Vec<NestedCell<T>>>,
+}
+
+impl<T> NestedGrid<T> {
+    fn new(cells: Vec<Vec<T>>) -> Self {
+        NestedGrid {
+            cells: cells
+                .into_iter()
+                .enumerate()
+                .map(|(x, row)| {
+                    row.into_iter()
+                        .enumerate()
+                        .map(|(y, value)| NestedCell {
+                            value,
+                            position: Position {
+                                x: x as i64,
+                                y: y as i64,
+                            },
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    fn at(&self, position: &Position) -> Option<&NestedCell<T>> {
+        if position.x < 0 || position.y < 0 {
+            return None;
+        }
+
+        if position.x as usize >= self.cells.len() || position.y as usize >= self.cells[0].len() {
+            return None;
+        }
+
+        Some(&self.cells[position.x as usize][position.y as usize])
+    }
+
+    fn neighbours(&self, position: &Position) -> Vec<&NestedCell<T>> {
+        let mut result = Vec::with_capacity(GRID_DIRECTIONS.len());
+        for movement in GRID_DIRECTIONS {
+            if let Some(cell) = self.at(&(position + movement)) {
+                result.push(cell)
+            }
+        }
+
+        result
+    }
+}
+
+fn report(name: &str, nested: Duration, flat: Duration) {
+    println!(
+        "  {:<24} nested {:>12.2?}   flat {:>12.2?}   {:>5.2}x",
+        name,
+        nested,
+        flat,
+        nested.as_secs_f64() / flat.as_secs_f64()
+    );
+}
+
+fn main() {
+    for size in [137, 1000] {
+        let values = rolls(size);
+        let nested = NestedGrid::new(values.clone());
+        let flat = Grid::new(values.clone());
+
+        println!("{0}x{0} grid", size);
+        println!(
+            "  {:<24} nested {:>10} B   flat {:>10} B",
+            "cell storage",
+            size * size * size_of::<NestedCell<bool>>(),
+            size * size * size_of::<bool>()
+        );
+
+        report(
+            "build",
+            bench(|| NestedGrid::new(values.clone())),
+            bench(|| Grid::new(values.clone())),
+        );
+
+        report(
+            "iterate",
+            bench(|| {
+                nested
+                    .cells
+                    .iter()
+                    .flatten()
+                    .filter(|cell| cell.value)
+                    .count()
+            }),
+            bench(|| flat.iter().filter(|cell| *cell.value).count()),
+        );
+
+        report(
+            "count roll neighbours",
+            bench(|| {
+                nested
+                    .cells
+                    .iter()
+                    .flatten()
+                    .filter(|cell| cell.value)
+                    .map(|cell| {
+                        nested
+                            .neighbours(&cell.position)
+                            .iter()
+                            .filter(|n| n.value)
+                            .count()
+                    })
+                    .sum::<usize>()
+            }),
+            bench(|| {
+                flat.iter()
+                    .filter(|cell| *cell.value)
+                    .map(|cell| {
+                        flat.neighbours(&cell.position)
+                            .iter()
+                            .filter(|n| *n.value)
+                            .count()
+                    })
+                    .sum::<usize>()
+            }),
+        );
+    }
+}
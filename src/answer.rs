@@ -0,0 +1,134 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+///
+/// Integers compare equal across variants, so `Answer::Int(5)` and
+/// `Answer::Unsigned(5)` are the same answer. Only integers are ordered.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Int(i64),
+    Unsigned(u128),
+    Text(String),
+}
+
+impl Answer {
+    /// Multi-line answers (e.g. letter art) are printed on their own lines.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Text(text) if text.contains('\n'))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Int(a), Answer::Unsigned(b)) | (Answer::Unsigned(b), Answer::Int(a)) => {
+                u128::try_from(*a).is_ok_and(|a| a == *b)
+            }
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Answer) -> Option<Ordering> {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => Some(a.cmp(b)),
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => Some(a.cmp(b)),
+            (Answer::Int(a), Answer::Unsigned(b)) => Some(match u128::try_from(*a) {
+                Ok(a) => a.cmp(b),
+                Err(_) => Ordering::Less,
+            }),
+            (Answer::Unsigned(_), Answer::Int(_)) => other.partial_cmp(self).map(Ordering::reverse),
+            (Answer::Text(a), Answer::Text(b)) if a == b => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Answer {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Int, i64, i8, i16, i32, i64);
+answer_from!(Unsigned, u128, u8, u16, u32, u64, usize, u128);
+
+impl PartialEq<i64> for Answer {
+    fn eq(&self, other: &i64) -> bool {
+        PartialEq::eq(self, &Answer::Int(*other))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Answer::Text(text) if text == other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(-12).to_string(), "-12");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("ABC").to_string(), "ABC");
+    }
+
+    #[test]
+    fn answer_equality_across_integer_types() {
+        assert_eq!(Answer::from(357u32), Answer::from(357i64));
+        assert_eq!(Answer::from(43usize), 43);
+        assert_ne!(Answer::from(-1), Answer::from(u128::MAX));
+        assert_ne!(Answer::from("5"), Answer::from(5));
+    }
+
+    #[test]
+    fn answer_ordering() {
+        assert!(Answer::from(-1) < Answer::from(0u8));
+        assert!(Answer::Unsigned(u128::MAX) > Answer::Int(i64::MAX));
+        assert!(Answer::from(3) < Answer::from(4));
+        assert_eq!(Answer::from("A").partial_cmp(&Answer::from("B")), None);
+        assert_eq!(Answer::from("A").partial_cmp(&Answer::from(1)), None);
+    }
+
+    #[test]
+    fn answer_multiline() {
+        assert!(Answer::from("#..#\n####").is_multiline());
+        assert!(!Answer::from(1).is_multiline());
+    }
+}
@@ -0,0 +1,218 @@
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
+
+use crate::answer::Answer;
+
+/// The known-good answers for a day, persisted as `answers/dayN.toml`:
+///
+/// ```toml
+/// part1 = 13
+/// part2 = "ABC"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Recorded {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: Answer },
+    New,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail { .. } => write!(f, "FAIL"),
+            Check::New => write!(f, "NEW"),
+        }
+    }
+}
+
+pub fn answers_path(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("day{}.toml", day))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => result.push('\\'),
+            Some('"') => result.push('"'),
+            Some('n') => result.push('\n'),
+            other => {
+                return Err(format!(
+                    "Invalid escape sequence: \\{}",
+                    other.unwrap_or(' ')
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub(crate) fn parse_value(value: &str) -> Result<Answer, String> {
+    if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        return Ok(Answer::Text(unescape(text)?));
+    }
+
+    if let Ok(int) = value.parse::<i64>() {
+        return Ok(Answer::Int(int));
+    }
+
+    value
+        .parse::<u128>()
+        .map(Answer::Unsigned)
+        .map_err(|_| format!("Invalid answer: {}", value))
+}
+
+pub(crate) fn format_value(answer: &Answer) -> String {
+    match answer {
+        Answer::Text(text) => format!("\"{}\"", escape(text)),
+        _ => answer.to_string(),
+    }
+}
+
+impl Recorded {
+    pub fn parse(text: &str) -> Result<Recorded, String> {
+        let mut recorded = Recorded::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("Line {}: expected `key = value`", number + 1))?;
+            let answer =
+                parse_value(value.trim()).map_err(|e| format!("Line {}: {}", number + 1, e))?;
+            match key.trim() {
+                "part1" => recorded.part1 = Some(answer),
+                "part2" => recorded.part2 = Some(answer),
+                other => return Err(format!("Line {}: unknown key {}", number + 1, other)),
+            }
+        }
+
+        Ok(recorded)
+    }
+
+    /// Loads the recorded answers for a day, treating a missing file as no answers.
+    pub fn load(dir: &Path, day: u32) -> Result<Recorded, String> {
+        let path = answers_path(dir, day);
+        match fs::read_to_string(&path) {
+            Ok(text) => Recorded::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Recorded::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, dir: &Path, day: u32) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(answers_path(dir, day), self.to_toml())
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = String::new();
+        if let Some(answer) = &self.part1 {
+            text += &format!("part1 = {}\n", format_value(answer));
+        }
+        if let Some(answer) = &self.part2 {
+            text += &format!("part2 = {}\n", format_value(answer));
+        }
+        text
+    }
+
+    pub fn part(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn check(&self, part: u32, actual: &Answer) -> Check {
+        match self.part(part) {
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.clone(),
+            },
+            None => Check::New,
+        }
+    }
+
+    /// Records answers for parts that have none yet. Existing answers are
+    /// never overwritten, so a regression can't replace a known-good value.
+    pub fn record_new(&mut self, part: u32, actual: &Answer) -> bool {
+        let slot = match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => return false,
+        };
+
+        if slot.is_some() {
+            return false;
+        }
+        *slot = Some(actual.clone());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_round_trip() {
+        let recorded = Recorded {
+            part1: Some(Answer::from(u128::MAX)),
+            part2: Some(Answer::from("#..#\n\"quoted\" \\")),
+        };
+
+        assert_eq!(Recorded::parse(&recorded.to_toml()).unwrap(), recorded);
+    }
+
+    #[test]
+    fn recorded_parse() {
+        let recorded = Recorded::parse("# day 4\npart1 = 13\n\npart2=43\n").unwrap();
+        assert_eq!(recorded.part1, Some(Answer::from(13)));
+        assert_eq!(recorded.part2, Some(Answer::from(43)));
+
+        assert!(Recorded::parse("part3 = 1").is_err());
+        assert!(Recorded::parse("part1 = abc").is_err());
+        assert!(Recorded::parse("part1 13").is_err());
+    }
+
+    #[test]
+    fn recorded_check() {
+        let mut recorded = Recorded::parse("part1 = 13").unwrap();
+        assert_eq!(recorded.check(1, &Answer::from(13usize)), Check::Pass);
+        assert_eq!(
+            recorded.check(1, &Answer::from(12)),
+            Check::Fail {
+                expected: Answer::from(13)
+            }
+        );
+        assert_eq!(recorded.check(2, &Answer::from(43)), Check::New);
+
+        assert!(!recorded.record_new(1, &Answer::from(12)));
+        assert!(recorded.record_new(2, &Answer::from(43)));
+        assert_eq!(recorded.check(2, &Answer::from(43)), Check::Pass);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{Cell, Grid, Neighbourhood, Position, SparseGrid};
+
+/// A collection of cells a cellular automaton can run over.
+pub trait Space {
+    /// What a rule sees and produces for one position.
+    type State: PartialEq;
+    /// The values neighbours are reported with.
+    type Value;
+
+    /// The positions whose state may change in the next generation, in the
+    /// order asynchronous updates visit them.
+    fn candidates(&self, neighbourhood: &Neighbourhood) -> Vec<Position>;
+    fn state(&self, position: &Position) -> Self::State;
+    /// The cells a rule sees around `position`: every in-bounds neighbour
+    /// for a `Grid`, and only the occupied ones for a `SparseGrid`.
+    fn neighbours(
+        &self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Cell<'_, Self::Value>>;
+    fn set(&mut self, position: &Position, state: Self::State);
+}
+
+/// Every cell of a grid is a candidate, visited in row-major order.
+impl<T: Clone + PartialEq> Space for Grid<T> {
+    type State = T;
+    type Value = T;
+
+    fn candidates(&self, _: &Neighbourhood) -> Vec<Position> {
+        (0..self.cells.len()).map(|i| self.position(i)).collect()
+    }
+
+    fn state(&self, position: &Position) -> T {
+        self.get(position).expect("Position is in the grid").clone()
+    }
+
+    fn neighbours(&self, position: &Position, neighbourhood: &Neighbourhood) -> Vec<Cell<'_, T>> {
+        self.neighbours_in(position, neighbourhood)
+    }
+
+    fn set(&mut self, position: &Position, state: T) {
+        *self.get_mut(position).expect("Position is in the grid") = state;
+    }
+}
+
+/// A sparse grid's state is `None` for empty positions, so rules can add
+/// and remove cells. Occupied cells and their neighbours are candidates,
+/// visited in sorted order.
+impl<T: Clone + PartialEq> Space for SparseGrid<T> {
+    type State = Option<T>;
+    type Value = T;
+
+    fn candidates(&self, neighbourhood: &Neighbourhood) -> Vec<Position> {
+        let mut positions: Vec<Position> = self
+            .cells
+            .keys()
+            .flat_map(|position| {
+                let mut around = neighbourhood.around(position);
+                around.push(*position);
+                around
+            })
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+
+    fn state(&self, position: &Position) -> Option<T> {
+        self.get(position).cloned()
+    }
+
+    fn neighbours(&self, position: &Position, neighbourhood: &Neighbourhood) -> Vec<Cell<'_, T>> {
+        self.neighbours_in(position, neighbourhood)
+    }
+
+    fn set(&mut self, position: &Position, state: Option<T>) {
+        match state {
+            Some(value) => {
+                self.insert(*position, value);
+            }
+            None => {
+                self.remove(position);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Every cell's next state is worked out from the previous generation.
+    Synchronous,
+    /// Cells are updated one at a time, each seeing earlier updates from the
+    /// same generation.
+    Asynchronous,
+}
+
+/// A cellular automaton: `rule` gives a cell's next state from its current
+/// state and its neighbours in `neighbourhood`, as `Space::neighbours`
+/// reports them.
+pub struct Automaton<F> {
+    pub neighbourhood: Neighbourhood,
+    pub update: Update,
+    rule: F,
+}
+
+impl<F> Automaton<F> {
+    pub fn new(neighbourhood: Neighbourhood, update: Update, rule: F) -> Self {
+        Automaton {
+            neighbourhood,
+            update,
+            rule,
+        }
+    }
+
+    /// Advances `space` by one generation, returning how many cells changed.
+    pub fn step<S: Space>(&self, space: &mut S) -> usize
+    where
+        F: Fn(&S::State, &[Cell<'_, S::Value>]) -> S::State,
+    {
+        let candidates = space.candidates(&self.neighbourhood);
+        let next_state = |space: &S, position: &Position| {
+            let state = space.state(position);
+            let next = (self.rule)(&state, &space.neighbours(position, &self.neighbourhood));
+            (next != state).then_some(next)
+        };
+
+        match self.update {
+            Update::Synchronous => {
+                let changes: Vec<(Position, S::State)> = candidates
+                    .iter()
+                    .filter_map(|position| {
+                        next_state(space, position).map(|next| (*position, next))
+                    })
+                    .collect();
+                let changed = changes.len();
+                for (position, state) in changes {
+                    space.set(&position, state);
+                }
+                changed
+            }
+            Update::Asynchronous => {
+                let mut changed = 0;
+                for position in &candidates {
+                    if let Some(next) = next_state(space, position) {
+                        space.set(position, next);
+                        changed += 1;
+                    }
+                }
+                changed
+            }
+        }
+    }
+
+    /// Runs at most `steps` generations, stopping early at a fixed point.
+    /// Returns the number of cells changed in each generation that changed
+    /// anything.
+    pub fn run<S: Space>(&self, space: &mut S, steps: usize) -> Vec<usize>
+    where
+        F: Fn(&S::State, &[Cell<'_, S::Value>]) -> S::State,
+    {
+        let mut changes = vec![];
+        for _ in 0..steps {
+            let changed = self.step(space);
+            if changed == 0 {
+                break;
+            }
+            changes.push(changed);
+        }
+        changes
+    }
+
+    /// Runs until a generation changes nothing, returning the number of
+    /// cells changed in each generation before that.
+    pub fn run_to_fixed_point<S: Space>(&self, space: &mut S) -> Vec<usize>
+    where
+        F: Fn(&S::State, &[Cell<'_, S::Value>]) -> S::State,
+    {
+        self.run(space, usize::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(alive: &Option<()>, neighbours: &[Cell<'_, ()>]) -> Option<()> {
+        match (alive, neighbours.len()) {
+            (Some(()), 2 | 3) | (None, 3) => Some(()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn sparse_life_glider() {
+        let grid = Grid::parse(".#.\n..#\n###").unwrap();
+        let mut cells: SparseGrid<()> = grid
+            .iter()
+            .filter(|cell| *cell.value == '#')
+            .map(|cell| (cell.position, ()))
+            .collect();
+        let start = cells.clone();
+
+        let automaton = Automaton::new(Neighbourhood::Moore, Update::Synchronous, life);
+        assert_eq!(automaton.run(&mut cells, 4).len(), 4);
+
+        // After four generations a glider has moved one step diagonally.
+        let moved: SparseGrid<()> = start
+            .iter()
+            .map(|cell| (cell.position + Position { x: 1, y: 1 }, ()))
+            .collect();
+        assert_eq!(cells, moved);
+    }
+
+    #[test]
+    fn grid_runs_to_fixed_point() {
+        // Each cell takes the largest value around it, so the 9 spreads one
+        // step per generation.
+        let mut grid = Grid::new(vec![vec![9, 0, 0, 0, 0]]);
+        let automaton = Automaton::new(
+            Neighbourhood::Orthogonal,
+            Update::Synchronous,
+            |&value: &u32, neighbours: &[Cell<'_, u32>]| {
+                neighbours
+                    .iter()
+                    .map(|cell| *cell.value)
+                    .fold(value, u32::max)
+            },
+        );
+
+        assert_eq!(automaton.run_to_fixed_point(&mut grid), vec![1, 1, 1, 1]);
+        assert!(grid.iter().all(|cell| *cell.value == 9));
+    }
+
+    #[test]
+    fn asynchronous_updates_see_earlier_changes() {
+        let mut grid = Grid::new(vec![vec![9, 0, 0, 0, 0]]);
+        let automaton = Automaton::new(
+            Neighbourhood::Orthogonal,
+            Update::Asynchronous,
+            |&value: &u32, neighbours: &[Cell<'_, u32>]| {
+                neighbours
+                    .iter()
+                    .map(|cell| *cell.value)
+                    .fold(value, u32::max)
+            },
+        );
+
+        // Row-major order carries the 9 across in a single generation.
+        assert_eq!(automaton.run_to_fixed_point(&mut grid), vec![4]);
+    }
+}
@@ -0,0 +1,256 @@
+use std::path::{Path, PathBuf};
+
+use aoc2025::client;
+
+pub const USAGE: &str = "Usage:
+    aoc2025 run --day <N> [--part <1|2>] [--input <PATH|-> | --example | --record]
+    aoc2025 run --all [--record]
+    aoc2025 new <N>
+    aoc2025 fetch <N>
+    aoc2025 submit --day <N> --part <1|2>";
+
+pub const DATA_DIR: &str = "data";
+
+pub fn default_input(day: u32) -> PathBuf {
+    client::input_path(Path::new(DATA_DIR), day)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    /// The cached puzzle input, `data/dayN.data`.
+    Default,
+    File(PathBuf),
+    Stdin,
+    /// The example from the puzzle text embedded in the day's solution.
+    Example,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub day: u32,
+    pub part: Option<u32>,
+    pub input: Input,
+    pub record: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    RunAll { record: bool },
+    New { day: u32 },
+    Fetch { day: u32 },
+    Submit { day: u32, part: u32 },
+}
+
+fn value_of(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn number_of(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<u32, String> {
+    let value = value_of(flag, args)?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn check_day(day: u32) -> Result<u32, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day must be between 1 and 25, got {}", day));
+    }
+    Ok(day)
+}
+
+fn check_part(part: u32) -> Result<u32, String> {
+    if part != 1 && part != 2 {
+        return Err(format!("Part must be 1 or 2, got {}", part));
+    }
+    Ok(part)
+}
+
+fn parse_submit(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(number_of(&arg, &mut args)?),
+            "--part" | "-p" => part = Some(number_of(&arg, &mut args)?),
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    Ok(Command::Submit {
+        day: check_day(day.ok_or("Missing required argument --day")?)?,
+        part: check_part(part.ok_or("Missing required argument --part")?)?,
+    })
+}
+
+fn parse_day_only(command: &str, mut args: impl Iterator<Item = String>) -> Result<u32, String> {
+    let day = args.next().ok_or(format!("Missing day for {}", command))?;
+    let day = day.parse().map_err(|_| format!("Invalid day: {}", day))?;
+    if let Some(arg) = args.next() {
+        return Err(format!("Unknown argument: {}", arg));
+    }
+
+    check_day(day)
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut example = false;
+    let mut record = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" | "-a" => all = true,
+            "--record" => record = true,
+            "--day" | "-d" => day = Some(number_of(&arg, &mut args)?),
+            "--part" | "-p" => part = Some(number_of(&arg, &mut args)?),
+            "--input" | "-i" => input = Some(value_of(&arg, &mut args)?),
+            "--example" | "-e" => example = true,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    if all {
+        if day.is_some() || part.is_some() || input.is_some() || example {
+            return Err(
+                "--all cannot be combined with --day, --part, --input or --example".to_string(),
+            );
+        }
+        return Ok(Command::RunAll { record });
+    }
+
+    let day = check_day(day.ok_or("Missing required argument --day")?)?;
+    let part = part.map(check_part).transpose()?;
+    // Recorded answers belong to the default input.
+    if record && (input.is_some() || example) {
+        return Err("--record cannot be combined with --input or --example".to_string());
+    }
+    let input = match (input, example) {
+        (Some(_), true) => return Err("--input cannot be combined with --example".to_string()),
+        (None, true) => Input::Example,
+        (Some(path), false) if path == "-" => Input::Stdin,
+        (Some(path), false) => Input::File(PathBuf::from(path)),
+        (None, false) => Input::Default,
+    };
+
+    Ok(Command::Run(RunArgs {
+        day,
+        part,
+        input,
+        record,
+    }))
+}
+
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("new") => Ok(Command::New {
+            day: parse_day_only("new", args)?,
+        }),
+        Some("fetch") => Ok(Command::Fetch {
+            day: parse_day_only("fetch", args)?,
+        }),
+        Some("submit") => parse_submit(args),
+        Some(other) => Err(format!("Unknown command: {}", other)),
+        None => Err("Missing command".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> {
+        line.split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parse_run_works() {
+        let command = parse(args("run --day 3 --part 2 --input other.data")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run(RunArgs {
+                day: 3,
+                part: Some(2),
+                input: Input::File(PathBuf::from("other.data")),
+                record: false,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_run_defaults_input() {
+        let Command::Run(run) = parse(args("run -d 4")).unwrap() else {
+            panic!("Expected a single day run");
+        };
+        assert_eq!(run.part, None);
+        assert_eq!(run.input, Input::Default);
+    }
+
+    #[test]
+    fn parse_run_inputs() {
+        let input = |line| match parse(args(line)).unwrap() {
+            Command::Run(run) => run.input,
+            _ => panic!("Expected a single day run"),
+        };
+
+        assert_eq!(input("run -d 1 -i -"), Input::Stdin);
+        assert_eq!(input("run -d 1 --example"), Input::Example);
+        assert!(parse(args("run -d 1 -e -i other.data")).is_err());
+        assert!(parse(args("run --all --example")).is_err());
+        assert!(parse(args("run -d 1 -e --record")).is_err());
+        assert!(parse(args("run -d 1 --record -i other.data")).is_err());
+        assert!(parse(args("run -d 1 --record")).is_ok());
+    }
+
+    #[test]
+    fn parse_run_rejects_bad_arguments() {
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run --day 26")).is_err());
+        assert!(parse(args("run --day 1 --part 3")).is_err());
+        assert!(parse(args("run --day")).is_err());
+        assert!(parse(args("walk --day 1")).is_err());
+    }
+
+    #[test]
+    fn parse_new_works() {
+        assert_eq!(parse(args("new 5")).unwrap(), Command::New { day: 5 });
+        assert!(parse(args("new")).is_err());
+        assert!(parse(args("new 0")).is_err());
+        assert!(parse(args("new 5 6")).is_err());
+        assert_eq!(parse(args("fetch 7")).unwrap(), Command::Fetch { day: 7 });
+    }
+
+    #[test]
+    fn parse_submit_works() {
+        assert_eq!(
+            parse(args("submit --day 4 --part 2")).unwrap(),
+            Command::Submit { day: 4, part: 2 }
+        );
+        assert!(parse(args("submit --day 4")).is_err());
+        assert!(parse(args("submit --part 1")).is_err());
+        assert!(parse(args("submit --day 4 --part 3")).is_err());
+    }
+
+    #[test]
+    fn parse_run_all() {
+        assert_eq!(
+            parse(args("run --all")).unwrap(),
+            Command::RunAll { record: false }
+        );
+        assert_eq!(
+            parse(args("run --record --all")).unwrap(),
+            Command::RunAll { record: true }
+        );
+        assert!(parse(args("run --all --day 1")).is_err());
+    }
+}
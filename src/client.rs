@@ -0,0 +1,312 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2025";
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// A client for the Advent of Code site. Requests are made with the `curl`
+/// binary, which must be on the `PATH`; the base URL can be pointed at any
+/// server speaking the same protocol.
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    /// Builds a client for `base_url`. Surrounding whitespace is trimmed
+    /// from `session`; a session with control characters left in it is
+    /// rejected, since it could break out of the config curl is given.
+    pub fn new(base_url: &str, session: &str) -> Result<Self, String> {
+        let session = session.trim();
+        if session.chars().any(char::is_control) {
+            return Err("The session token must not contain control characters".to_string());
+        }
+
+        Ok(Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        })
+    }
+
+    /// Builds a client from `AOC_TOKEN` and, optionally, `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, String> {
+        let session = env::var("AOC_TOKEN").map_err(|_| "AOC_TOKEN is not set".to_string())?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or(DEFAULT_BASE_URL.to_string());
+        Client::new(&base_url, &session)
+    }
+
+    pub fn request(&self, path: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut command = Command::new("curl");
+        command
+            .arg("--silent")
+            .arg("--show-error")
+            .args(["--user-agent", "github.com/actuary/aoc-2025"])
+            .args(["--write-out", "\n%{http_code}"])
+            // The session cookie is passed as config on stdin so it doesn't
+            // show up in the process list.
+            .args(["--config", "-"]);
+        for (key, value) in form {
+            command.args(["--data-urlencode", &format!("{}={}", key, value)]);
+        }
+
+        let mut child = command
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => {
+                    "curl was not found; it is needed to talk to the Advent of Code site"
+                        .to_string()
+                }
+                _ => format!("Failed to run curl: {}", e),
+            })?;
+
+        let config = format!("cookie = \"session={}\"\n", escape_config(&self.session));
+        child
+            .stdin
+            .take()
+            .expect("curl stdin is piped")
+            .write_all(config.as_bytes())
+            .map_err(|e| format!("Failed to send the session to curl: {}", e))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Request to {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or(format!("Malformed response from {}", url))?;
+        let status = status
+            .parse()
+            .map_err(|_| format!("Malformed status from {}: {}", url, status))?;
+
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+
+    pub fn input(&self, day: u32) -> Result<String, String> {
+        let response = self.request(&format!("/day/{}/input", day), &[])?;
+        if response.status != 200 {
+            return Err(format!(
+                "Failed to download day {}: HTTP {}: {}",
+                day,
+                response.status,
+                response.body.trim()
+            ));
+        }
+
+        if is_html(&response.body) {
+            return Err(format!(
+                "Failed to download day {}: got an HTML page instead of an input (is AOC_TOKEN valid?)",
+                day
+            ));
+        }
+
+        if response.body.trim().is_empty() {
+            return Err(format!("Failed to download day {}: empty input", day));
+        }
+
+        Ok(response.body)
+    }
+}
+
+/// Escapes `value` for a double-quoted string in a curl config file.
+fn escape_config(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn is_html(body: &str) -> bool {
+    let start = body.trim_start().to_lowercase();
+    start.starts_with("<!doctype") || start.starts_with("<html")
+}
+
+pub fn input_path(data_dir: &Path, day: u32) -> PathBuf {
+    data_dir.join(format!("day{}.data", day))
+}
+
+/// Returns the input for a day, downloading it only if it isn't cached in
+/// `data_dir` yet. An empty cached file (as made by `new`) is not an input.
+/// The flag is true if the input was downloaded.
+pub fn fetch_input(client: &Client, data_dir: &Path, day: u32) -> Result<(String, bool), String> {
+    let path = input_path(data_dir, day);
+    if let Ok(contents) = fs::read_to_string(&path)
+        && !contents.trim().is_empty()
+    {
+        return Ok((contents, false));
+    }
+
+    let contents = client.input(day)?;
+    fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Failed to create {}: {}", data_dir.display(), e))?;
+    fs::write(&path, &contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok((contents, true))
+}
+
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::process::Command;
+    use std::thread::{self, JoinHandle};
+
+    /// Whether `curl`, which requests go through, is missing. Tests talking
+    /// to the stub server skip themselves when it is.
+    pub fn curl_missing() -> bool {
+        let missing = Command::new("curl").arg("--version").output().is_err();
+        if missing {
+            eprintln!("Skipping: curl is not installed");
+        }
+        missing
+    }
+
+    /// A request as seen by the stub server.
+    pub struct Request {
+        pub head: String,
+        pub body: String,
+    }
+
+    /// Serves the given `(status, body)` responses in order, one per
+    /// connection, and returns the requests it received.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head += &line;
+                }
+
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                requests.push(Request {
+                    head,
+                    body: String::from_utf8(request_body).unwrap(),
+                });
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2025-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetch_input_downloads_once() {
+        if stub::curl_missing() {
+            return;
+        }
+        let (url, server) = stub::serve(vec![(200, "L68\nL30\n")]);
+        let client = Client::new(&url, "secret").unwrap();
+        let data = temp_dir("fetch");
+
+        assert_eq!(
+            fetch_input(&client, &data, 1).unwrap(),
+            ("L68\nL30\n".to_string(), true)
+        );
+        // The stub only answers once, so a second download would fail.
+        assert_eq!(
+            fetch_input(&client, &data, 1).unwrap(),
+            ("L68\nL30\n".to_string(), false)
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].head.starts_with("GET /day/1/input "));
+        assert!(requests[0].head.contains("session=secret"));
+        assert!(requests[0].head.contains("github.com/actuary/aoc-2025"));
+        fs::remove_dir_all(&data).unwrap();
+    }
+
+    #[test]
+    fn fetch_input_rejects_html_and_errors() {
+        if stub::curl_missing() {
+            return;
+        }
+        let (url, server) = stub::serve(vec![
+            (200, "<!DOCTYPE html>\n<html>Log in</html>"),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+        ]);
+        let client = Client::new(&url, "secret").unwrap();
+        let data = temp_dir("fetch-errors");
+
+        assert!(fetch_input(&client, &data, 2).unwrap_err().contains("HTML"));
+        assert!(
+            fetch_input(&client, &data, 2)
+                .unwrap_err()
+                .contains("HTTP 404")
+        );
+        assert!(!input_path(&data, 2).exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn escape_config_quotes() {
+        assert_eq!(escape_config("abc123"), "abc123");
+        assert_eq!(escape_config(r#"a"b\c"#), r#"a\"b\\c"#);
+
+        let client = Client::new("https://example.com/", " abc123\n").unwrap();
+        assert_eq!(client.session, "abc123");
+        assert_eq!(client.base_url, "https://example.com");
+        assert!(Client::new("https://example.com", "abc\n--output x").is_err());
+        assert!(Client::new("https://example.com", "abc\0def").is_err());
+    }
+}
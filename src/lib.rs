@@ -1,11 +1,35 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::{fmt, ops, slice};
 
+use parse::ParseError;
+
+pub mod answer;
+pub mod answers;
+pub mod automaton;
+pub mod client;
+pub mod parse;
+pub mod problems;
+pub mod region;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+
 #[derive(Debug, Hash, Eq, PartialEq, Copy, Clone, PartialOrd, Ord)]
 pub struct Position {
     pub x: i64,
     pub y: i64,
 }
 
+impl Position {
+    /// The number of orthogonal steps between `self` and `other`.
+    pub fn manhattan(&self, other: &Position) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
 impl fmt::Display for Position {
     // This trait requires `fmt` with this exact signature.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -35,7 +59,7 @@ impl ops::Mul<&Position> for i64 {
     }
 }
 
-impl<'a, 'b> ops::Add<&'b Position> for &'a Position {
+impl<'b> ops::Add<&'b Position> for &Position {
     type Output = Position;
 
     fn add(self, _rhs: &'b Position) -> Position {
@@ -46,7 +70,7 @@ impl<'a, 'b> ops::Add<&'b Position> for &'a Position {
     }
 }
 
-impl<'a> ops::Add<Position> for &'a Position {
+impl ops::Add<Position> for &Position {
     type Output = Position;
 
     fn add(self, _rhs: Position) -> Position {
@@ -79,16 +103,19 @@ impl ops::Mul<Position> for i64 {
     }
 }
 
+/// Wraps a position into `0..size` on each axis, so `position % &size` is
+/// always inside a grid of that size, even for negative coordinates.
 impl ops::Rem<&Position> for Position {
     type Output = Position;
 
     fn rem(self, _rhs: &Position) -> Position {
         Position {
-            x: _rhs.x.rem_euclid(self.x),
-            y: _rhs.y.rem_euclid(self.y),
+            x: self.x.rem_euclid(_rhs.x),
+            y: self.y.rem_euclid(_rhs.y),
         }
     }
 }
+
 impl ops::Sub<Position> for Position {
     type Output = Position;
 
@@ -111,142 +138,676 @@ pub const GRID_DIRECTIONS: [Position; 8] = [
     Position { x: 1, y: 1 },
 ];
 
+/// Which cells count as adjacent to a position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The four cells sharing an edge.
+    Orthogonal,
+    /// The four cells sharing only a corner.
+    Diagonal,
+    /// All eight surrounding cells.
+    Moore,
+    /// Cells within the given Manhattan distance.
+    Manhattan(i64),
+    /// Cells within the given Chebyshev distance (a square of that radius).
+    Chebyshev(i64),
+    /// Arbitrary offsets from the position. Repeated offsets count once.
+    Custom(Vec<Position>),
+}
+
+impl Neighbourhood {
+    /// The offsets making up the neighbourhood, each at most once. Apart
+    /// from `Custom`, these are in row-major order and never include the
+    /// position itself.
+    pub fn offsets(&self) -> Vec<Position> {
+        fn within(radius: i64, inside: impl Fn(i64, i64) -> bool) -> Vec<Position> {
+            (-radius..=radius)
+                .flat_map(|x| (-radius..=radius).map(move |y| Position { x, y }))
+                .filter(|p| *p != Position { x: 0, y: 0 } && inside(p.x.abs(), p.y.abs()))
+                .collect()
+        }
+
+        match self {
+            Neighbourhood::Orthogonal => within(1, |x, y| x + y == 1),
+            Neighbourhood::Diagonal => within(1, |x, y| x == 1 && y == 1),
+            Neighbourhood::Moore => GRID_DIRECTIONS.to_vec(),
+            Neighbourhood::Manhattan(radius) => within(*radius, |x, y| x + y <= *radius),
+            Neighbourhood::Chebyshev(radius) => within(*radius, |_, _| true),
+            Neighbourhood::Custom(offsets) => {
+                let mut seen = HashSet::new();
+                offsets
+                    .iter()
+                    .copied()
+                    .filter(|offset| seen.insert(*offset))
+                    .collect()
+            }
+        }
+    }
+
+    /// The neighbouring positions of `position`, unbounded.
+    pub fn around(&self, position: &Position) -> Vec<Position> {
+        self.offsets()
+            .iter()
+            .map(|offset| position + offset)
+            .collect()
+    }
+}
+
+/// A view of one grid cell: a reference to its value and its position.
 #[derive(Debug)]
-pub struct Cell<T> {
-    pub value: T,
+pub struct Cell<'a, T> {
+    pub value: &'a T,
     pub position: Position,
 }
 
+impl<T> Clone for Cell<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Cell<'_, T> {}
+
+/// A mutable view of one grid cell.
+#[derive(Debug)]
+pub struct CellMut<'a, T> {
+    pub value: &'a mut T,
+    pub position: Position,
+}
+
+/// A rectangular grid stored row-major in a single `Vec`. Positions are
+/// `(row, column)`, so `x` indexes rows and `y` indexes columns.
 #[derive(Debug)]
 pub struct Grid<T> {
-    pub cells: Vec<Vec<Cell<T>>>,
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
 }
 
+/// The positions of marker characters (such as start and end symbols) found
+/// while parsing a grid.
+pub type Markers = HashMap<char, Vec<Position>>;
+
 impl<T> Grid<T> {
+    /// Builds a grid from `cells`, panicking if the rows differ in length.
+    /// Use `try_new` for rows that haven't been checked.
     pub fn new(cells: Vec<Vec<T>>) -> Self {
-        if cells.len() == 0 {
-            return Grid { cells: vec![] };
-        }
+        Grid::try_new(cells).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        if cells.len() > 1 && cells.iter().any(|row| row.len() != cells[0].len()) {
-            panic!("Grid rows must be the same size")
+    /// Builds a grid from `cells`, failing if the rows differ in length.
+    pub fn try_new(cells: Vec<Vec<T>>) -> Result<Self, String> {
+        if let Some(x) = cells.iter().position(|row| row.len() != cells[0].len()) {
+            return Err(format!(
+                "Grid rows must be the same size: row {} has {} cells, row 0 has {}",
+                x,
+                cells[x].len(),
+                cells[0].len()
+            ));
         }
 
-        Grid {
-            cells: cells
-                .into_iter()
-                .enumerate()
-                .map(|(x, row)| {
-                    row.into_iter()
-                        .enumerate()
-                        .map(|(y, val)| Cell {
-                            value: val,
-                            position: Position {
+        let height = cells.len();
+        let width = cells.first().map_or(0, |row| row.len());
+        Ok(Grid {
+            cells: cells.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// `try_new` for rows parsed from `data`, with a failure reported
+    /// without a location.
+    fn from_parsed(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        Grid::try_new(rows).map_err(|e| ParseError::new(0, 0, "", &e))
+    }
+
+    /// Parses `data` as rows of characters mapped by `cell`. Ragged rows and
+    /// characters `cell` rejects are reported as errors; CRLF input is fine.
+    pub fn from_str_with(data: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self, ParseError> {
+        Grid::from_parsed(parse::char_grid(data, cell)?)
+    }
+
+    /// Like `from_str_with`, also returning where each of the `markers`
+    /// characters occurs. Marker characters are still mapped by `cell`.
+    pub fn from_str_with_markers(
+        data: &str,
+        markers: &[char],
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<(Self, Markers), ParseError> {
+        let rows = parse::char_grid(data, |c| cell(c).map(|value| (value, c)))?;
+
+        let mut found = Markers::new();
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(x, row)| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(y, (value, c))| {
+                        if markers.contains(&c) {
+                            let position = Position {
                                 x: x as i64,
                                 y: y as i64,
-                            },
-                        })
-                        .collect()
-                })
-                .collect(),
-        }
+                            };
+                            found.entry(c).or_default().push(position);
+                        }
+                        value
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok((Grid::from_parsed(rows)?, found))
     }
 
-    pub fn at(&self, position: &Position) -> Option<&Cell<T>> {
+    fn index(&self, position: &Position) -> Option<usize> {
         if position.x < 0 || position.y < 0 {
             return None;
         }
 
-        if position.x as usize >= self.cells.len() || position.y as usize >= self.cells[0].len() {
+        let (x, y) = (position.x as usize, position.y as usize);
+        if x >= self.height || y >= self.width {
             return None;
         }
 
-        return Some(&self.cells[position.x as usize][position.y as usize]);
+        Some(x * self.width + y)
     }
 
-    pub fn at_mut(&mut self, position: &Position) -> Option<&mut Cell<T>> {
-        if position.x < 0 || position.y < 0 {
-            return None;
+    fn position(&self, index: usize) -> Position {
+        Position {
+            x: (index / self.width) as i64,
+            y: (index % self.width) as i64,
         }
+    }
 
-        if position.x as usize >= self.cells.len() || position.y as usize >= self.cells[0].len() {
-            return None;
-        }
+    /// The cell at `position`, or `None` if it is outside the grid.
+    pub fn at(&self, position: &Position) -> Option<Cell<'_, T>> {
+        self.get(position).map(|value| Cell {
+            value,
+            position: *position,
+        })
+    }
+
+    pub fn at_mut(&mut self, position: &Position) -> Option<CellMut<'_, T>> {
+        let position = *position;
+        self.get_mut(&position)
+            .map(|value| CellMut { value, position })
+    }
+
+    /// The value at `position`, or `None` if it is outside the grid.
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.index(position).map(|i| &self.cells[i])
+    }
 
-        return Some(&mut self.cells[position.x as usize][position.y as usize]);
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.index(position).map(|i| &mut self.cells[i])
     }
 
-    pub fn neighbours(&self, position: &Position) -> Vec<&Cell<T>> {
-        let mut result = vec![];
+    /// The in-bounds cells of the Moore (8-way) neighbourhood of `position`.
+    pub fn neighbours(&self, position: &Position) -> Vec<Cell<'_, T>> {
+        let mut result = Vec::with_capacity(GRID_DIRECTIONS.len());
         for movement in GRID_DIRECTIONS {
-            match self.at(&(position + movement)) {
-                Some(cell) => result.push(cell),
-                None => (),
+            if let Some(cell) = self.at(&(position + movement)) {
+                result.push(cell)
             }
         }
 
         result
     }
 
+    /// The in-bounds positions in `neighbourhood` of `position`.
+    pub fn neighbour_positions(
+        &self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Position> {
+        neighbourhood
+            .around(position)
+            .into_iter()
+            .filter(|p| self.index(p).is_some())
+            .collect()
+    }
+
+    /// The in-bounds cells in `neighbourhood` of `position`.
+    pub fn neighbours_in(
+        &self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Cell<'_, T>> {
+        self.neighbour_positions(position, neighbourhood)
+            .into_iter()
+            .map(|p| Cell {
+                value: &self.cells[self.index(&p).unwrap()],
+                position: p,
+            })
+            .collect()
+    }
+
+    /// The in-bounds cells in `neighbourhood` of `position`, mutably. Cells
+    /// are returned in row-major order, each at most once.
+    pub fn neighbours_mut(
+        &mut self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<CellMut<'_, T>> {
+        let mut indices: Vec<usize> = neighbourhood
+            .around(position)
+            .iter()
+            .filter_map(|p| self.index(p))
+            .collect();
+        indices.sort_unstable();
+
+        let width = self.width;
+        let mut result = Vec::with_capacity(indices.len());
+        let mut rest = self.cells.as_mut_slice();
+        let mut offset = 0;
+        for index in indices {
+            let (cell, tail) = rest[index - offset..].split_first_mut().unwrap();
+            result.push(CellMut {
+                value: cell,
+                position: Position {
+                    x: (index / width) as i64,
+                    y: (index % width) as i64,
+                },
+            });
+            rest = tail;
+            offset = index + 1;
+        }
+
+        result
+    }
+
     pub fn size(&self) -> Position {
-        if self.cells.len() == 0 {
-            return Position { x: 0, y: 0 };
+        Position {
+            x: self.height as i64,
+            y: self.width as i64,
         }
+    }
 
-        return Position {
-            x: self.cells.len() as i64,
-            y: self.cells[0].len() as i64,
-        };
+    /// The cells in row-major order.
+    pub fn iter(&self) -> GridIntoIterator<'_, T> {
+        self.into_iter()
+    }
+
+    /// A grid of the same size with each cell replaced by `f` of it.
+    pub fn map<U>(&self, f: impl Fn(Cell<'_, T>) -> U) -> Grid<U> {
+        Grid {
+            cells: self.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
     }
 }
 
-impl<'a, T> IntoIterator for &'a Grid<T> {
-    type Item = &'a Cell<T>;
-    type IntoIter = GridIntoIterator<'a, T>;
+/// A view of a grid whose opposite edges are connected, so every position,
+/// including negative ones, refers to a cell.
+pub struct WrappingGrid<'a, T> {
+    grid: &'a Grid<T>,
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        GridIntoIterator {
-            grid: self,
-            index: Position { x: 0, y: 0 },
+impl<T> Grid<T> {
+    pub fn wrapping(&self) -> WrappingGrid<'_, T> {
+        WrappingGrid { grid: self }
+    }
+}
+
+impl<'a, T> WrappingGrid<'a, T> {
+    /// The position inside the grid that `position` refers to. `None` if
+    /// the grid is empty.
+    pub fn wrap(&self, position: &Position) -> Option<Position> {
+        if self.grid.cells.is_empty() {
+            return None;
         }
+
+        Some(*position % &self.grid.size())
+    }
+
+    /// The cell at `position`, with its position wrapped into the grid;
+    /// only `None` if the grid is empty.
+    pub fn at(&self, position: &Position) -> Option<Cell<'a, T>> {
+        self.wrap(position)
+            .and_then(|position| self.grid.at(&position))
+    }
+
+    /// The value at `position`; only `None` if the grid is empty.
+    pub fn get(&self, position: &Position) -> Option<&'a T> {
+        self.at(position).map(|cell| cell.value)
+    }
+
+    /// The Moore neighbourhood of `position`, with positions wrapped into the
+    /// grid. On grids narrower than three cells a cell can appear twice.
+    pub fn neighbours(&self, position: &Position) -> Vec<Cell<'a, T>> {
+        self.neighbours_in(position, &Neighbourhood::Moore)
+    }
+
+    pub fn neighbours_in(
+        &self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Cell<'a, T>> {
+        neighbourhood
+            .around(position)
+            .iter()
+            .filter_map(|p| self.at(p))
+            .collect()
+    }
+
+    /// Walks from `start` in steps of `step` forever, wrapping at the edges.
+    /// The walk is empty if the grid is.
+    pub fn walk(&self, start: &Position, step: Position) -> impl Iterator<Item = Cell<'a, T>> {
+        let grid = WrappingGrid { grid: self.grid };
+        let mut position = *start;
+        std::iter::from_fn(move || {
+            let cell = grid.at(&position);
+            position = position + step;
+            cell
+        })
+    }
+
+    /// The cells in row-major order, as for the underlying grid.
+    pub fn iter(&self) -> GridIntoIterator<'a, T> {
+        self.grid.iter()
     }
 }
 
-impl<'a, T> Iterator for GridIntoIterator<'a, T> {
-    type Item = &'a Cell<T>;
-    fn next(&mut self) -> Option<&'a Cell<T>> {
-        if self.grid.size() == (Position { x: 0, y: 0 }) {
+/// A view of a grid repeated infinitely in every direction. Unlike
+/// `WrappingGrid`, positions keep their global coordinates, so a search can
+/// tell copies of the same cell apart.
+pub struct TiledGrid<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn tiled(&self) -> TiledGrid<'_, T> {
+        TiledGrid { grid: self }
+    }
+}
+
+impl<'a, T> TiledGrid<'a, T> {
+    /// Which copy of the grid `position` falls in; the original is `(0, 0)`.
+    /// `None` if the grid is empty.
+    pub fn tile(&self, position: &Position) -> Option<Position> {
+        if self.grid.cells.is_empty() {
             return None;
         }
 
-        if self.index.x > self.grid.size().x - 1 || self.index.y > self.grid.size().y - 1 {
+        let size = self.grid.size();
+        Some(Position {
+            x: position.x.div_euclid(size.x),
+            y: position.y.div_euclid(size.y),
+        })
+    }
+
+    /// The position within its tile that `position` corresponds to. `None`
+    /// if the grid is empty.
+    pub fn local(&self, position: &Position) -> Option<Position> {
+        if self.grid.cells.is_empty() {
             return None;
         }
 
-        let result = self
-            .grid
-            .at(&self.index)
-            .expect("Iterator position not in bounds.");
+        Some(*position % &self.grid.size())
+    }
+
+    /// The cell at `position`, keeping its global position; only `None` if
+    /// the grid is empty.
+    pub fn at(&self, position: &Position) -> Option<Cell<'a, T>> {
+        self.get(position).map(|value| Cell {
+            value,
+            position: *position,
+        })
+    }
+
+    /// The value at `position`; only `None` if the grid is empty.
+    pub fn get(&self, position: &Position) -> Option<&'a T> {
+        self.local(position)
+            .and_then(|position| self.grid.get(&position))
+    }
+
+    /// The Moore neighbourhood of `position`, with global positions.
+    pub fn neighbours(&self, position: &Position) -> Vec<Cell<'a, T>> {
+        self.neighbours_in(position, &Neighbourhood::Moore)
+    }
+
+    /// The cells in `neighbourhood` of `position`, with global positions.
+    pub fn neighbours_in(
+        &self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Cell<'a, T>> {
+        neighbourhood
+            .around(position)
+            .into_iter()
+            .filter_map(|position| self.at(&position))
+            .collect()
+    }
+}
+
+/// A grid of cells at arbitrary positions, including negative ones, backed
+/// by a `HashMap`. Counts the occupied cells in each row and column, so
+/// the bounding box stays cheap to find as cells change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+    rows: BTreeMap<i64, usize>,
+    columns: BTreeMap<i64, usize>,
+}
 
-        if self.index.y >= self.grid.size().y - 1 {
-            self.index = Position {
-                x: self.index.x + 1,
-                y: 0,
-            };
-        } else {
-            self.index = Position {
-                x: self.index.x,
-                y: self.index.y + 1,
-            };
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            rows: BTreeMap::new(),
+            columns: BTreeMap::new(),
         }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cells of `grid` for which `keep` is true, at the same positions.
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|cell| keep(cell.value))
+            .map(|cell| (cell.position, cell.value.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
 
-        Some(result)
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.cells.contains_key(position)
+    }
+
+    /// The cell at `position`, or `None` if it is unoccupied.
+    pub fn at(&self, position: &Position) -> Option<Cell<'_, T>> {
+        self.get(position).map(|value| Cell {
+            value,
+            position: *position,
+        })
+    }
+
+    pub fn at_mut(&mut self, position: &Position) -> Option<CellMut<'_, T>> {
+        let position = *position;
+        self.get_mut(&position)
+            .map(|value| CellMut { value, position })
+    }
+
+    /// The value at `position`, or `None` if it is unoccupied.
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.cells.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.cells.get_mut(position)
+    }
+
+    /// Sets the cell at `position`, returning its previous value.
+    pub fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        let previous = self.cells.insert(position, value);
+        if previous.is_none() {
+            *self.rows.entry(position.x).or_default() += 1;
+            *self.columns.entry(position.y).or_default() += 1;
+        }
+        previous
+    }
+
+    /// Clears the cell at `position`, returning its value.
+    pub fn remove(&mut self, position: &Position) -> Option<T> {
+        let value = self.cells.remove(position)?;
+        for (counts, key) in [
+            (&mut self.rows, position.x),
+            (&mut self.columns, position.y),
+        ] {
+            let count = counts.get_mut(&key).expect("Occupied cells are counted");
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&key);
+            }
+        }
+
+        Some(value)
+    }
+
+    /// The smallest and largest corners of the box containing every cell, or
+    /// `None` if the grid is empty.
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        let min = Position {
+            x: *self.rows.first_key_value()?.0,
+            y: *self.columns.first_key_value()?.0,
+        };
+        let max = Position {
+            x: *self.rows.last_key_value()?.0,
+            y: *self.columns.last_key_value()?.0,
+        };
+        Some((min, max))
+    }
+
+    /// The occupied cells of the Moore (8-way) neighbourhood of `position`.
+    pub fn neighbours(&self, position: &Position) -> Vec<Cell<'_, T>> {
+        self.neighbours_in(position, &Neighbourhood::Moore)
+    }
+
+    /// The occupied cells in `neighbourhood` of `position`.
+    pub fn neighbours_in(
+        &self,
+        position: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Cell<'_, T>> {
+        neighbourhood
+            .around(position)
+            .into_iter()
+            .filter_map(|position| self.at(&position))
+            .collect()
+    }
+
+    /// The occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Cell<'_, T>> {
+        self.cells.iter().map(|(position, value)| Cell {
+            value,
+            position: *position,
+        })
+    }
+
+    /// A dense grid covering the bounding box, with `empty` in unoccupied
+    /// cells, and the minimum corner of the box. The grid is shifted so that
+    /// corner lands on `(0, 0)`; add it to a grid position to get back the
+    /// sparse one.
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Position)
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds() else {
+            return (Grid::new(vec![]), Position { x: 0, y: 0 });
+        };
+
+        let rows = (min.x..=max.x)
+            .map(|x| {
+                (min.y..=max.y)
+                    .map(|y| self.get(&Position { x, y }).unwrap_or(&empty).clone())
+                    .collect()
+            })
+            .collect();
+        (Grid::new(rows), min)
+    }
+
+    /// Draws the bounding box one row per line, mapping each position with
+    /// `cell`, which gets `None` for unoccupied positions.
+    pub fn render(&self, cell: impl Fn(Option<&T>) -> char) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+
+        (min.x..=max.x)
+            .map(|x| {
+                (min.y..=max.y)
+                    .map(|y| cell(self.get(&Position { x, y })))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Position, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Position, T)>>(&mut self, iter: I) {
+        for (position, value) in iter {
+            self.insert(position, value);
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(data: &str) -> Result<Self, ParseError> {
+        Grid::from_str_with(data, Some)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = Cell<'a, T>;
+    type IntoIter = GridIntoIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GridIntoIterator {
+            grid: self,
+            cells: self.cells.iter().enumerate(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for GridIntoIterator<'a, T> {
+    type Item = Cell<'a, T>;
+    fn next(&mut self) -> Option<Cell<'a, T>> {
+        let (index, value) = self.cells.next()?;
+        Some(Cell {
+            value,
+            position: self.grid.position(index),
+        })
     }
 }
 
 pub struct GridIntoIterator<'a, T> {
     grid: &'a Grid<T>,
-    index: Position,
+    cells: std::iter::Enumerate<slice::Iter<'a, T>>,
 }
 
 pub fn gcd(a: i64, b: i64) -> i64 {
@@ -288,6 +849,7 @@ pub enum Direction {
 }
 
 impl Direction {
+    #[allow(clippy::result_unit_err)]
     pub fn from(c: char) -> Result<Direction, ()> {
         match c {
             '^' => Ok(Direction::North),
@@ -353,15 +915,49 @@ mod tests {
     fn grid_create() {
         let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![3, 2, 1]];
         let grid = Grid::new(values);
-        assert_eq!(grid.cells.len(), 2);
-        assert_eq!(grid.cells[0].len(), 3);
+        assert_eq!(grid.size(), Position { x: 2, y: 3 });
     }
 
     #[test]
     fn grid_empty() {
         let values: Vec<Vec<i64>> = vec![];
         let grid = Grid::new(values);
-        assert_eq!(grid.cells.len(), 0);
+        assert_eq!(grid.size(), Position { x: 0, y: 0 });
+
+        let grid: Grid<i64> = Grid::new(vec![vec![], vec![]]);
+        assert_eq!(grid.size(), Position { x: 2, y: 0 });
+        assert_eq!(grid.iter().count(), 0);
+    }
+
+    #[test]
+    fn grid_at_and_get() {
+        let mut grid = Grid::new(vec![vec![1, 2, 3], vec![3, 2, 1]]);
+        let position = Position { x: 1, y: 0 };
+        let cell = grid.at(&position).unwrap();
+        assert_eq!((cell.position, *cell.value), (position, 3));
+        assert_eq!(grid.get(&position), Some(&3));
+
+        *grid.at_mut(&position).unwrap().value = 4;
+        *grid.get_mut(&Position { x: 0, y: 2 }).unwrap() += 1;
+        assert_eq!(grid.get(&position), Some(&4));
+        assert_eq!(grid.get(&Position { x: 0, y: 2 }), Some(&4));
+        assert!(grid.at(&Position { x: 2, y: 0 }).is_none());
+    }
+
+    #[test]
+    fn grid_try_new_rejects_ragged_rows() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![3, 2]];
+        assert_eq!(
+            Grid::try_new(values).unwrap_err(),
+            "Grid rows must be the same size: row 1 has 2 cells, row 0 has 3"
+        );
+        assert!(Grid::try_new(vec![vec![1], vec![2]]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Grid rows must be the same size")]
+    fn grid_new_panics_on_ragged_rows() {
+        Grid::new(vec![vec![1, 2], vec![3]]);
     }
 
     #[test]
@@ -416,6 +1012,359 @@ mod tests {
         assert_eq!(neighbours.iter().map(|cell| cell.value).sum::<i64>(), 19);
     }
 
+    #[test]
+    fn grid_parse() {
+        let grid = Grid::parse("ab\r\ncd\r\n").unwrap();
+        assert_eq!(grid.size(), Position { x: 2, y: 2 });
+        assert_eq!(grid.get(&Position { x: 1, y: 0 }), Some(&'c'));
+    }
+
+    #[test]
+    fn grid_from_str_with_errors() {
+        let wall = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let error = Grid::from_str_with("#.#\n#.\n", wall).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = Grid::from_str_with("#.#\n#?#\n", wall).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn grid_from_str_with_markers() {
+        let (grid, markers) =
+            Grid::from_str_with_markers("S.#\n.#E\n..E", &['S', 'E'], |c| Some(c == '#')).unwrap();
+
+        assert_eq!(grid.size(), Position { x: 3, y: 3 });
+        assert_eq!(markers[&'S'], vec![Position { x: 0, y: 0 }]);
+        assert_eq!(
+            markers[&'E'],
+            vec![Position { x: 1, y: 2 }, Position { x: 2, y: 2 }]
+        );
+        assert_eq!(grid.get(&Position { x: 0, y: 0 }), Some(&false));
+    }
+
+    #[test]
+    fn neighbourhood_offsets() {
+        let origin = Position { x: 0, y: 0 };
+        assert_eq!(
+            Neighbourhood::Orthogonal.offsets(),
+            vec![
+                Position { x: -1, y: 0 },
+                Position { x: 0, y: -1 },
+                Position { x: 0, y: 1 },
+                Position { x: 1, y: 0 }
+            ]
+        );
+        assert_eq!(Neighbourhood::Diagonal.offsets().len(), 4);
+        assert_eq!(
+            Neighbourhood::Moore.offsets(),
+            Neighbourhood::Chebyshev(1).offsets()
+        );
+        assert_eq!(
+            Neighbourhood::Orthogonal.offsets(),
+            Neighbourhood::Manhattan(1).offsets()
+        );
+        assert_eq!(Neighbourhood::Manhattan(2).offsets().len(), 12);
+        assert_eq!(Neighbourhood::Chebyshev(2).offsets().len(), 24);
+        assert!(!Neighbourhood::Chebyshev(3).offsets().contains(&origin));
+        assert_eq!(
+            Neighbourhood::Custom(vec![Position { x: 2, y: 1 }]).around(&Position { x: 1, y: 1 }),
+            vec![Position { x: 3, y: 2 }]
+        );
+    }
+
+    #[test]
+    fn grid_neighbours_in() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let grid = Grid::new(values);
+
+        let sum = |neighbourhood, position| {
+            grid.neighbours_in(&position, &neighbourhood)
+                .iter()
+                .map(|cell| cell.value)
+                .sum::<i64>()
+        };
+        assert_eq!(sum(Neighbourhood::Orthogonal, Position { x: 1, y: 1 }), 20);
+        assert_eq!(sum(Neighbourhood::Diagonal, Position { x: 1, y: 1 }), 20);
+        assert_eq!(sum(Neighbourhood::Orthogonal, Position { x: 0, y: 0 }), 6);
+        assert_eq!(
+            sum(Neighbourhood::Manhattan(2), Position { x: 0, y: 0 }),
+            2 + 3 + 4 + 5 + 7
+        );
+
+        assert_eq!(
+            grid.neighbour_positions(&Position { x: 2, y: 2 }, &Neighbourhood::Diagonal),
+            vec![Position { x: 1, y: 1 }]
+        );
+    }
+
+    #[test]
+    fn grid_neighbours_mut() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let mut grid = Grid::new(values);
+
+        let offsets = vec![
+            Position { x: 0, y: 1 },
+            Position { x: 0, y: 1 },
+            Position { x: 1, y: 0 },
+        ];
+        for cell in grid.neighbours_mut(&Position { x: 1, y: 1 }, &Neighbourhood::Custom(offsets)) {
+            *cell.value *= 10;
+        }
+
+        let values: Vec<i64> = grid.iter().map(|cell| *cell.value).collect();
+        assert_eq!(values, vec![1, 2, 3, 4, 5, 60, 7, 80, 9]);
+    }
+
+    #[test]
+    fn custom_neighbourhood_repeats_count_once() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        let centre = Position { x: 1, y: 1 };
+        let custom = Neighbourhood::Custom(vec![
+            Position { x: 1, y: 0 },
+            Position { x: 0, y: 1 },
+            Position { x: 1, y: 0 },
+        ]);
+
+        assert_eq!(
+            custom.offsets(),
+            vec![Position { x: 1, y: 0 }, Position { x: 0, y: 1 }]
+        );
+        let values: Vec<i64> = grid
+            .neighbours_in(&centre, &custom)
+            .iter()
+            .map(|cell| *cell.value)
+            .collect();
+        assert_eq!(values, vec![8, 6]);
+        assert_eq!(grid.wrapping().neighbours_in(&centre, &custom).len(), 2);
+    }
+
+    #[test]
+    fn position_rem_wraps() {
+        let size = Position { x: 3, y: 4 };
+        assert_eq!(Position { x: 7, y: 5 } % &size, Position { x: 1, y: 1 });
+        assert_eq!(Position { x: -1, y: -5 } % &size, Position { x: 2, y: 3 });
+    }
+
+    #[test]
+    fn wrapping_grid_at() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let grid = Grid::new(values);
+        let wrapping = grid.wrapping();
+
+        assert_eq!(wrapping.get(&Position { x: 3, y: 0 }), Some(&1));
+        assert_eq!(wrapping.get(&Position { x: -1, y: -1 }), Some(&9));
+        assert_eq!(wrapping.get(&Position { x: -4, y: 7 }), Some(&8));
+        assert_eq!(
+            wrapping.wrap(&Position { x: -4, y: 7 }),
+            Some(Position { x: 2, y: 1 })
+        );
+
+        let empty: Grid<i64> = Grid::new(vec![]);
+        assert_eq!(empty.wrapping().get(&Position { x: 0, y: 0 }), None);
+    }
+
+    #[test]
+    fn wrapping_grid_neighbours() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let grid = Grid::new(values);
+        let wrapping = grid.wrapping();
+
+        let neighbours = wrapping.neighbours(&Position { x: 0, y: 0 });
+        assert_eq!(neighbours.len(), 8);
+        assert_eq!(
+            neighbours.iter().map(|cell| cell.value).sum::<i64>(),
+            45 - 1
+        );
+        assert!(
+            neighbours
+                .iter()
+                .any(|cell| cell.position == Position { x: 2, y: 2 })
+        );
+
+        let orthogonal =
+            wrapping.neighbours_in(&Position { x: -3, y: 2 }, &Neighbourhood::Orthogonal);
+        let values: Vec<i64> = orthogonal.iter().map(|cell| *cell.value).collect();
+        assert_eq!(values, vec![9, 2, 1, 6]);
+    }
+
+    #[test]
+    fn wrapping_grid_walk() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let grid = Grid::new(values);
+
+        let walked: Vec<i64> = grid
+            .wrapping()
+            .walk(&Position { x: 0, y: -1 }, Position { x: 1, y: 1 })
+            .take(6)
+            .map(|cell| *cell.value)
+            .collect();
+        assert_eq!(walked, vec![3, 4, 2, 6, 1, 5]);
+        assert_eq!(grid.wrapping().iter().count(), 6);
+    }
+
+    #[test]
+    fn wrapping_grid_empty() {
+        let grid: Grid<i64> = Grid::new(vec![]);
+        let wrapping = grid.wrapping();
+        let position = Position { x: -1, y: 2 };
+
+        assert_eq!(wrapping.wrap(&position), None);
+        assert!(wrapping.neighbours(&position).is_empty());
+        assert!(
+            wrapping
+                .neighbours_in(&position, &Neighbourhood::Orthogonal)
+                .is_empty()
+        );
+        assert_eq!(wrapping.walk(&position, Position { x: 1, y: 0 }).count(), 0);
+    }
+
+    #[test]
+    fn tiled_grid_at_and_tile() {
+        let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let grid = Grid::new(values);
+        let tiled = grid.tiled();
+
+        assert_eq!(tiled.get(&Position { x: 1, y: 2 }), Some(&6));
+        assert_eq!(tiled.get(&Position { x: 5, y: -1 }), Some(&6));
+        assert_eq!(
+            tiled.tile(&Position { x: 1, y: 2 }),
+            Some(Position { x: 0, y: 0 })
+        );
+        assert_eq!(
+            tiled.tile(&Position { x: 5, y: -1 }),
+            Some(Position { x: 2, y: -1 })
+        );
+        assert_eq!(
+            tiled.tile(&Position { x: -2, y: -3 }),
+            Some(Position { x: -1, y: -1 })
+        );
+        assert_eq!(
+            tiled.local(&Position { x: -2, y: -3 }),
+            Some(Position { x: 0, y: 0 })
+        );
+    }
+
+    #[test]
+    fn tiled_grid_empty() {
+        let grid: Grid<i64> = Grid::new(vec![]);
+        let tiled = grid.tiled();
+        let position = Position { x: 3, y: -1 };
+
+        assert_eq!(tiled.tile(&position), None);
+        assert_eq!(tiled.local(&position), None);
+        assert_eq!(tiled.get(&position), None);
+        assert!(tiled.neighbours(&position).is_empty());
+    }
+
+    #[test]
+    fn tiled_grid_search() {
+        // The open cell right of the wall can't be reached inside one copy,
+        // but its copy in the tile to the left joins up with the start.
+        let grid = Grid::parse("####\n.S#.\n####").unwrap();
+        let tiled = grid.tiled();
+
+        let start = Position { x: 1, y: 1 };
+        let reached = search::bfs(start, |position| {
+            tiled
+                .neighbours_in(position, &Neighbourhood::Orthogonal)
+                .into_iter()
+                .filter(|cell| *cell.value != '#')
+                .map(|cell| cell.position)
+                .collect::<Vec<_>>()
+        });
+
+        let across = Position { x: 1, y: -1 };
+        assert_eq!(reached.distances.len(), 3);
+        assert_eq!(reached.distance(&across), Some(2));
+        assert_eq!(tiled.tile(&across), Some(Position { x: 0, y: -1 }));
+        assert_eq!(tiled.local(&across), Some(Position { x: 1, y: 3 }));
+    }
+
+    #[test]
+    fn sparse_grid_tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Position { x: 0, y: 0 }, 'a');
+        grid.insert(Position { x: -2, y: 3 }, 'b');
+        grid.insert(Position { x: 1, y: 1 }, 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some((Position { x: -2, y: 0 }, Position { x: 1, y: 3 }))
+        );
+
+        assert_eq!(grid.remove(&Position { x: -2, y: 3 }), Some('b'));
+        assert_eq!(grid.remove(&Position { x: -2, y: 3 }), None);
+        assert_eq!(
+            grid.bounds(),
+            Some((Position { x: 0, y: 0 }, Position { x: 1, y: 1 }))
+        );
+
+        // Overwriting a cell doesn't count it twice.
+        assert_eq!(grid.insert(Position { x: 1, y: 1 }, 'd'), Some('c'));
+        grid.remove(&Position { x: 1, y: 1 });
+        assert_eq!(
+            grid.bounds(),
+            Some((Position { x: 0, y: 0 }, Position { x: 0, y: 0 }))
+        );
+
+        grid.remove(&Position { x: 0, y: 0 });
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn sparse_grid_neighbours() {
+        let grid: SparseGrid<i64> = [(-1, -1, 1), (0, 1, 2), (5, 5, 3)]
+            .into_iter()
+            .map(|(x, y, v)| (Position { x, y }, v))
+            .collect();
+
+        let mut values: Vec<i64> = grid
+            .neighbours(&Position { x: 0, y: 0 })
+            .iter()
+            .map(|cell| *cell.value)
+            .collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2]);
+
+        let orthogonal = grid.neighbours_in(&Position { x: 0, y: 0 }, &Neighbourhood::Orthogonal);
+        assert_eq!(orthogonal.len(), 1);
+        assert_eq!(orthogonal[0].position, Position { x: 0, y: 1 });
+    }
+
+    #[test]
+    fn sparse_grid_converts_and_renders() {
+        let grid = Grid::parse("..#\n.#.\n...").unwrap();
+        let sparse = SparseGrid::from_grid(&grid, |c| *c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.render(|c| c.map_or('.', |_| '#')), ".#\n#.");
+
+        let (dense, offset) = sparse.to_grid('.');
+        assert_eq!(dense.size(), Position { x: 2, y: 2 });
+        assert_eq!(offset, Position { x: 0, y: 1 });
+        assert_eq!(dense.get(&Position { x: 0, y: 1 }), Some(&'#'));
+        for cell in dense.iter().filter(|cell| *cell.value == '#') {
+            assert!(sparse.contains(&(cell.position + offset)));
+        }
+        assert_eq!(SparseGrid::<char>::new().render(|_| '#'), "");
+    }
+
+    #[test]
+    fn grid_map() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mapped = grid.map(|cell| *cell.value * 10 + cell.position.y);
+
+        assert_eq!(mapped.size(), grid.size());
+        assert_eq!(mapped.get(&Position { x: 1, y: 2 }), Some(&62));
+    }
+
     #[test]
     fn grid_iterator() {
         let values: Vec<Vec<i64>> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
@@ -424,5 +1373,16 @@ mod tests {
 
         let res: i64 = grid.into_iter().map(|c| c.value).sum();
         assert_eq!(res, 45);
+
+        let positions: Vec<Position> = grid.iter().map(|c| c.position).take(4).collect();
+        assert_eq!(
+            positions,
+            vec![
+                Position { x: 0, y: 0 },
+                Position { x: 0, y: 1 },
+                Position { x: 0, y: 2 },
+                Position { x: 1, y: 0 }
+            ]
+        );
     }
 }
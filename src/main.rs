@@ -1,12 +1,289 @@
-use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::{env, fs, io, process};
 
-use problems::day4::{part1, part2};
+use aoc2025::answer::Answer;
+use aoc2025::answers::{Check, Recorded};
+use aoc2025::client::{self, Client};
+use aoc2025::runner::RunError;
+use aoc2025::solution::{Day, Parsed};
+use aoc2025::submit::{Log, Outcome, Submission};
+use aoc2025::{problems, runner, scaffold};
+use cli::{Command, Input};
 
-pub mod problems;
+mod cli;
+
+const ANSWERS_DIR: &str = "answers";
+
+fn print_answer(part: u32, answer: &Answer, check: Option<&Check>) {
+    let status = match check {
+        Some(Check::Fail { expected }) => format!(" (FAIL, expected {})", expected),
+        Some(check) => format!(" ({})", check),
+        None => String::new(),
+    };
+
+    if answer.is_multiline() {
+        println!("Part {}{}:\n{}", part, status, answer);
+    } else {
+        println!("Part {}: {}{}", part, answer, status);
+    }
+}
+
+fn record(recorded: &mut Recorded, day: u32, answers: &[(u32, &Answer)]) -> Result<(), String> {
+    let mut changed = false;
+    for (part, answer) in answers {
+        changed |= recorded.record_new(*part, answer);
+    }
+
+    if changed {
+        recorded
+            .save(Path::new(ANSWERS_DIR), day)
+            .map_err(|e| format!("Failed to record answers for day {}: {}", day, e))?;
+    }
+    Ok(())
+}
+
+fn read_input(input: &Input, day: &Day) -> Result<String, String> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+
+    match input {
+        Input::Default => read(&cli::default_input(day.number)),
+        Input::File(path) => read(path),
+        Input::Stdin => {
+            let mut contents = String::new();
+            io::stdin()
+                .read_to_string(&mut contents)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            Ok(contents)
+        }
+        Input::Example => Ok(day.example.to_string()),
+    }
+}
+
+fn parse_input(day: &Day, contents: &str) -> Result<Box<dyn Parsed>, String> {
+    (day.parse)(contents).map_err(|e| {
+        format!(
+            "Failed to parse day {} input: {}",
+            day.number,
+            e.render(contents)
+        )
+    })
+}
+
+fn run(args: cli::RunArgs) -> Result<(), String> {
+    let day = problems::lookup(args.day).ok_or(format!("Day {} is not implemented", args.day))?;
+
+    let contents = read_input(&args.input, day)?;
+
+    // Recorded answers and submissions belong to the default input, so other
+    // inputs aren't checked.
+    let checked = args.input == Input::Default;
+    let mut recorded = Recorded::load(Path::new(ANSWERS_DIR), args.day)?;
+    let log = Log::load(Path::new(cli::DATA_DIR), args.day)?;
+
+    let parsed = parse_input(day, &contents)?;
+    let mut answers = vec![];
+    for part in [1, 2] {
+        if args.part.is_some_and(|p| p != part) {
+            continue;
+        }
+
+        let answer = if part == 1 {
+            parsed.part1()
+        } else {
+            parsed.part2()
+        }
+        .map_err(|e| format!("Part {} failed: {}", part, e))?;
+        let check = checked.then(|| recorded.check(part, &answer));
+        print_answer(part, &answer, check.as_ref());
+        if checked && let Some(warning) = log.check(part, &answer) {
+            eprintln!("Warning: part {}: {}", part, warning);
+        }
+        answers.push((part, answer, check));
+    }
+
+    if checked && args.record {
+        let new: Vec<(u32, &Answer)> = answers
+            .iter()
+            .map(|(part, answer, _)| (*part, answer))
+            .collect();
+        record(&mut recorded, args.day, &new)?;
+    }
+
+    if answers
+        .iter()
+        .any(|(_, _, check)| matches!(check, Some(Check::Fail { .. })))
+    {
+        return Err("Answer does not match the recorded answer".to_string());
+    }
+    Ok(())
+}
+
+fn run_all(record_new: bool) -> Result<(), String> {
+    let mut reports = vec![];
+    let mut warnings = vec![];
+    let mut unrun = vec![];
+    for day in problems::DAYS {
+        let path = cli::default_input(day.number);
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                let mut recorded = Recorded::load(Path::new(ANSWERS_DIR), day.number)?;
+                let report = match runner::run(day, &contents, &recorded) {
+                    Ok(report) => report,
+                    Err(RunError::Parse(e)) => {
+                        eprintln!(
+                            "Failed to parse day {} input: {}",
+                            day.number,
+                            e.render(&contents)
+                        );
+                        unrun.push(day.number.to_string());
+                        continue;
+                    }
+                    Err(RunError::Part { part, message }) => {
+                        eprintln!("Day {} part {} failed: {}", day.number, part, message);
+                        unrun.push(day.number.to_string());
+                        continue;
+                    }
+                };
+                let log = Log::load(Path::new(cli::DATA_DIR), day.number)?;
+                for (part, answer) in [(1, &report.part1.answer), (2, &report.part2.answer)] {
+                    if let Some(warning) = log.check(part, answer) {
+                        warnings.push(format!(
+                            "Warning: day {} part {}: {}",
+                            day.number, part, warning
+                        ));
+                    }
+                }
+                if record_new {
+                    let answers = [(1, &report.part1.answer), (2, &report.part2.answer)];
+                    record(&mut recorded, day.number, &answers)?;
+                }
+                reports.push(report);
+            }
+            Err(e) => eprintln!(
+                "Skipping day {}: failed to read {}: {}",
+                day.number,
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    println!("{}", runner::summary_table(&reports));
+    for warning in warnings {
+        eprintln!("{}", warning);
+    }
+
+    let failed: Vec<String> = reports
+        .iter()
+        .filter(|report| report.failed())
+        .map(|report| report.day.to_string())
+        .collect();
+    if !unrun.is_empty() {
+        return Err(format!("Failed to run day(s) {}", unrun.join(", ")));
+    }
+    if !failed.is_empty() {
+        return Err(format!(
+            "Recorded answers failed for day(s) {}",
+            failed.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+fn fetch(day: u32) -> Result<(), String> {
+    let client = Client::from_env()?;
+    let path = cli::default_input(day);
+    let (_, downloaded) = client::fetch_input(&client, Path::new(cli::DATA_DIR), day)?;
+    if downloaded {
+        println!("Downloaded {}", path.display());
+    } else {
+        println!("{} is already cached", path.display());
+    }
+    Ok(())
+}
+
+fn new(day: u32) -> Result<(), String> {
+    for step in scaffold::new_day(Path::new("."), day)? {
+        println!("{}", step);
+    }
+
+    if Client::from_env().is_err() {
+        println!("AOC_TOKEN is not set, skipping input download");
+        return Ok(());
+    }
+    fetch(day)
+}
+
+fn submit(day_number: u32, part: u32) -> Result<(), String> {
+    let day =
+        problems::lookup(day_number).ok_or(format!("Day {} is not implemented", day_number))?;
+    let client = Client::from_env()?;
+    let data_dir = Path::new(cli::DATA_DIR);
+
+    let mut log = Log::load(data_dir, day_number)?;
+    if let Some(answer) = log.solved(part) {
+        return Err(format!(
+            "Day {} part {} is already solved with {}",
+            day_number, part, answer
+        ));
+    }
+
+    let (contents, _) = client::fetch_input(&client, data_dir, day_number)?;
+    let parsed = parse_input(day, &contents)?;
+    let answer = if part == 1 {
+        parsed.part1()
+    } else {
+        parsed.part2()
+    }
+    .map_err(|e| format!("Part {} failed: {}", part, e))?;
+    if let Some(warning) = log.check(part, &answer) {
+        return Err(format!("Refusing to submit {}: {}", answer, warning));
+    }
+    println!("Submitting {} for day {} part {}", answer, day_number, part);
+
+    let outcome = client.submit(day_number, part, &answer)?;
+    println!("{}", outcome);
+
+    log.append(
+        data_dir,
+        day_number,
+        Submission {
+            part,
+            outcome,
+            answer: answer.clone(),
+        },
+    )
+    .map_err(|e| format!("Failed to record submission: {}", e))?;
+
+    if outcome == Outcome::Right {
+        let mut recorded = Recorded::load(Path::new(ANSWERS_DIR), day_number)?;
+        record(&mut recorded, day_number, &[(part, &answer)])?;
+    }
+    Ok(())
+}
 
 fn main() {
-    let contents = fs::read_to_string("data/day4.data").expect("Failed to read file");
+    let command = match cli::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::Run(args) => run(args),
+        Command::RunAll { record } => run_all(record),
+        Command::New { day } => new(day),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part } => submit(day, part),
+    };
 
-    println!("Part 1: {}", part1(&contents[..]));
-    println!("Part 2: {}", part2(&contents[..]));
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
 }
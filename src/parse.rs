@@ -0,0 +1,322 @@
+use std::str::FromStr;
+use std::{error, fmt};
+
+/// A malformed piece of puzzle input, located by 1-based line and column.
+/// Line and column are 0 when the location is unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: &str) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Builds an error for `text`, which should be a slice of `data`; its
+    /// position in `data` gives the line and column. Text from elsewhere
+    /// gets line 0, column 0.
+    pub fn at(data: &str, text: &str, message: &str) -> Self {
+        let Some(offset) = (text.as_ptr() as usize)
+            .checked_sub(data.as_ptr() as usize)
+            .filter(|&offset| offset + text.len() <= data.len())
+        else {
+            return ParseError::new(0, 0, text, message);
+        };
+
+        let before = &data[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError::new(line, column, text, message)
+    }
+
+    /// Formats the error followed by the offending line of `data` with the
+    /// error's text underlined.
+    pub fn render(&self, data: &str) -> String {
+        let Some(line) = self
+            .line
+            .checked_sub(1)
+            .and_then(|index| data.lines().nth(index))
+        else {
+            return self.to_string();
+        };
+
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let underline = "^".repeat(self.text.chars().count().max(1));
+        format!(
+            "{}\n {} | {}\n {} | {}{}",
+            self,
+            number,
+            line,
+            gutter,
+            " ".repeat(self.column.saturating_sub(1)),
+            underline
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}: {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// The lines of `text` without trailing whitespace (so CRLF input works),
+/// skipping blank lines at the start and end.
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.trim_start_matches(['\r', '\n'])
+        .trim_end()
+        .lines()
+        .map(str::trim_end)
+}
+
+/// Splits `text` into blocks separated by one or more blank lines.
+pub fn blocks(text: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content.is_empty() {
+            if let Some(s) = start.take() {
+                blocks.push(&text[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(s) = start {
+        blocks.push(&text[s..end]);
+    }
+    blocks
+}
+
+/// Parses `text`, a slice of `data`, ignoring surrounding whitespace.
+pub fn number<T: FromStr>(data: &str, text: &str) -> Result<T, ParseError> {
+    let trimmed = text.trim();
+    trimmed
+        .parse()
+        .map_err(|_| ParseError::at(data, trimmed, "invalid number"))
+}
+
+/// Extracts every integer in `text`, a slice of `data`. A `-` is a sign
+/// unless it follows a digit, so `x=-3` gives -3 but `1-2` gives 1 and 2.
+pub fn integers(data: &str, text: &str) -> Result<Vec<i64>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut result = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        result.push(number(data, &text[start..i])?);
+    }
+
+    Ok(result)
+}
+
+/// Parses an inclusive range written `a-b`, where `text` is a slice of `data`.
+pub fn range(data: &str, text: &str) -> Result<(i64, i64), ParseError> {
+    let text = text.trim();
+    // Skip the first character so a negative start isn't taken as the dash.
+    let dash = text
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '-')
+        .map(|(i, _)| i)
+        .ok_or_else(|| ParseError::at(data, text, "expected a range like 11-22"))?;
+
+    Ok((
+        number(data, &text[..dash])?,
+        number(data, &text[dash + 1..])?,
+    ))
+}
+
+/// Parses every character of `text`, a slice of `data`, as a decimal digit.
+pub fn digits(data: &str, text: &str) -> Result<Vec<u32>, ParseError> {
+    text.char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::at(data, &text[i..i + c.len_utf8()], "expected a digit"))
+        })
+        .collect()
+}
+
+/// Parses `data` as rows of equal length, mapping each character with
+/// `cell`. Characters `cell` rejects are reported as errors.
+pub fn char_grid<T>(
+    data: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let rows: Vec<&str> = lines(data).collect();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+
+    rows.iter()
+        .map(|row| {
+            if row.chars().count() != width {
+                return Err(ParseError::at(data, row, "rows must be the same length"));
+            }
+
+            row.char_indices()
+                .map(|(i, c)| {
+                    cell(c).ok_or_else(|| {
+                        ParseError::at(data, &row[i..i + c.len_utf8()], "unexpected character")
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_at_locates_text() {
+        let data = "L68\nL30\nXR48";
+        let error = ParseError::at(data, &data[8..9], "expected L or R");
+        assert_eq!(error, ParseError::new(3, 1, "X", "expected L or R"));
+
+        let error = ParseError::at(data, &data[5..7], "bad number");
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.to_string(), "line 2, column 2: bad number: \"30\"");
+    }
+
+    #[test]
+    fn parse_error_at_counts_characters() {
+        let data = "ab\nµµx";
+        let error = ParseError::at(data, &data[7..], "unexpected");
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn parse_error_render() {
+        let data = "L68\nL3x0\n";
+        let error = ParseError::at(data, &data[5..8], "bad number");
+        assert_eq!(
+            error.render(data),
+            "line 2, column 2: bad number: \"3x0\"\n 2 | L3x0\n   |  ^^^"
+        );
+    }
+
+    #[test]
+    fn lines_handles_crlf_and_blank_ends() {
+        let data = "\r\nL68  \r\nL30\r\n\r\n";
+        assert_eq!(lines(data).collect::<Vec<_>>(), vec!["L68", "L30"]);
+        assert_eq!(lines("").count(), 0);
+    }
+
+    #[test]
+    fn blocks_split_on_blank_lines() {
+        let data = "\na\nb\n\n  \r\nc\r\n\n";
+        assert_eq!(blocks(data), vec!["a\nb", "c"]);
+        assert!(blocks("\n\n").is_empty());
+    }
+
+    #[test]
+    fn number_works() {
+        assert_eq!(number::<u8>("x 12 ", &"x 12 "[1..]), Ok(12));
+
+        let data = "1\n-3\n99999";
+        assert_eq!(number::<i64>(data, &data[2..4]), Ok(-3));
+        assert_eq!(
+            number::<u8>(data, &data[5..]),
+            Err(ParseError::new(3, 1, "99999", "invalid number"))
+        );
+    }
+
+    #[test]
+    fn integers_extracts_signed_values() {
+        let data = "p=0,-4 v=3,-3\n11-22";
+        assert_eq!(integers(data, data), Ok(vec![0, -4, 3, -3, 11, 22]));
+        assert_eq!(integers(data, "no numbers"), Ok(vec![]));
+    }
+
+    #[test]
+    fn range_works() {
+        let data = "11-22,-5--3, 7-x,12";
+        assert_eq!(range(data, &data[..5]), Ok((11, 22)));
+        assert_eq!(range(data, &data[6..11]), Ok((-5, -3)));
+        assert_eq!(range(data, &data[12..16]).unwrap_err().text, "x");
+        assert_eq!(
+            range(data, &data[17..]),
+            Err(ParseError::new(1, 18, "12", "expected a range like 11-22"))
+        );
+    }
+
+    #[test]
+    fn digits_works() {
+        let data = "987\n81x";
+        assert_eq!(digits(data, &data[..3]), Ok(vec![9, 8, 7]));
+        assert_eq!(
+            digits(data, &data[4..]),
+            Err(ParseError::new(2, 3, "x", "expected a digit"))
+        );
+    }
+
+    #[test]
+    fn char_grid_works() {
+        let at = |c| match c {
+            '@' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        assert_eq!(
+            char_grid("@.\r\n.@\r\n", at),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(
+            char_grid("@.\n.", at),
+            Err(ParseError::new(2, 1, ".", "rows must be the same length"))
+        );
+        assert_eq!(
+            char_grid("@.\n.x", at),
+            Err(ParseError::new(2, 2, "x", "unexpected character"))
+        );
+    }
+
+    #[test]
+    fn parse_error_without_location() {
+        let data = "abc";
+        let error = ParseError::at(data, "other", "unexpected");
+        assert_eq!(error, ParseError::new(0, 0, "other", "unexpected"));
+        assert_eq!(error.render(data), error.to_string());
+
+        let error = ParseError::new(1, 0, "a", "unexpected");
+        assert!(error.render(data).ends_with(" | ^"));
+    }
+}
@@ -1,38 +1,33 @@
-enum Rotation {
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub enum Rotation {
     L(i64),
     R(i64),
 }
 
-fn parse(data: &str) -> Vec<Rotation> {
-    data.trim()
-        .split("\n")
+pub struct Day1;
+
+fn parse(data: &str) -> Result<Vec<Rotation>, ParseError> {
+    parse::lines(data)
         .map(|line| {
-            if line.starts_with('L') {
-                Rotation::L(line[1..].parse().unwrap())
+            if let Some(text) = line.strip_prefix('L') {
+                Ok(Rotation::L(parse::number(data, text)?))
+            } else if let Some(text) = line.strip_prefix('R') {
+                Ok(Rotation::R(parse::number(data, text)?))
             } else {
-                Rotation::R(line[1..].parse().unwrap())
+                Err(ParseError::at(
+                    data,
+                    line,
+                    "expected a rotation starting with L or R",
+                ))
             }
         })
         .collect()
 }
 
-pub fn part1(data: &str) -> i64 {
-    let rotations = parse(data);
-    let dial_start = 50;
-    let result = rotations.iter().scan(dial_start, |acc, val| {
-        match val {
-            Rotation::L(i) => *acc = (*acc - i).rem_euclid(100),
-            Rotation::R(i) => *acc = (*acc + i).rem_euclid(100),
-        };
-
-        Some(*acc)
-    });
-
-    result.filter(|v| *v == 0).count() as i64
-}
-
-pub fn solve(data: &str, dial_start: i64) -> i64 {
-    let rotations = parse(data);
+pub fn solve(rotations: &[Rotation], dial_start: i64) -> i64 {
     rotations
         .iter()
         .scan((dial_start, 0), |acc, val| {
@@ -68,21 +63,13 @@ pub fn solve(data: &str, dial_start: i64) -> i64 {
             Some(*acc)
         })
         .last()
-        .unwrap()
-        .1 as i64
+        .map_or(0, |(_, count)| count)
 }
 
-pub fn part2(data: &str) -> i64 {
-    solve(data, 50)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Day1 {
+    type Input = Vec<Rotation>;
 
-    #[test]
-    fn part1_works() {
-        let data = "L68
+    const EXAMPLE: &'static str = "L68
 L30
 R48
 L5
@@ -92,21 +79,56 @@ L1
 L99
 R14
 L82";
-        assert_eq!(part1(data), 3);
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
+
+    fn part1(rotations: &Self::Input) -> Result<Answer, String> {
+        let dial_start = 50;
+        let result = rotations.iter().scan(dial_start, |acc, val| {
+            match val {
+                Rotation::L(i) => *acc = (*acc - i).rem_euclid(100),
+                Rotation::R(i) => *acc = (*acc + i).rem_euclid(100),
+            };
+
+            Some(*acc)
+        });
+
+        Ok(result.filter(|v| *v == 0).count().into())
+    }
+
+    fn part2(rotations: &Self::Input) -> Result<Answer, String> {
+        Ok(solve(rotations, 50).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(
+            Day1::part1(&Day1::parse(Day1::EXAMPLE).unwrap()).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn parse_reports_location() {
+        let error = Day1::parse("L68\nL30\n\nR48").err().unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = Day1::parse("L68\r\nR4x8\r\n").err().unwrap();
+        assert_eq!(error, ParseError::new(2, 2, "4x8", "invalid number"));
     }
 
     #[test]
     fn part2_works() {
-        let data = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
-        assert_eq!(part2(data), 6);
+        assert_eq!(
+            Day1::part2(&Day1::parse(Day1::EXAMPLE).unwrap()).unwrap(),
+            6
+        );
     }
 }
@@ -1,13 +1,15 @@
 use std::collections::HashSet;
 
-fn parse(data: &str) -> Vec<(i64, i64)> {
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub struct Day2;
+
+fn parse(data: &str) -> Result<Vec<(i64, i64)>, ParseError> {
     data.trim()
         .split(",")
-        .map(|line| {
-            let ids = line.split("-");
-            let ids_int: Vec<i64> = ids.map(|val| val.parse().unwrap()).collect();
-            (ids_int[0], ids_int[1])
-        })
+        .map(|range| parse::range(data, range))
         .collect()
 }
 
@@ -25,16 +27,6 @@ pub fn is_invalid_id(val: i64) -> bool {
     left == right
 }
 
-pub fn part1(data: &str) -> i64 {
-    let result: i64 = parse(data)
-        .iter()
-        .map(|&(first, last)| (first..=last).filter(|&v| is_invalid_id(v)))
-        .flatten()
-        .sum();
-
-    result
-}
-
 pub fn is_invalid_id_part2(val: i64) -> bool {
     if val < 10 {
         return false;
@@ -46,7 +38,7 @@ pub fn is_invalid_id_part2(val: i64) -> bool {
             continue;
         }
 
-        if val_str.len() % i == 0 {
+        if val_str.len().is_multiple_of(i) {
             if i == 1 {
                 let unique_chars: HashSet<char> = val_str.chars().collect();
                 return unique_chars.len() == 1;
@@ -71,14 +63,32 @@ pub fn is_invalid_id_part2(val: i64) -> bool {
     false
 }
 
-pub fn part2(data: &str) -> i64 {
-    let result: i64 = parse(data)
-        .iter()
-        .map(|&(first, last)| (first..=last).filter(|&v| is_invalid_id_part2(v)))
-        .flatten()
-        .sum();
+impl Solution for Day2 {
+    type Input = Vec<(i64, i64)>;
+
+    const EXAMPLE: &'static str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<Answer, String> {
+        let result: i64 = ranges
+            .iter()
+            .flat_map(|&(first, last)| (first..=last).filter(|&v| is_invalid_id(v)))
+            .sum();
+
+        Ok(result.into())
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<Answer, String> {
+        let result: i64 = ranges
+            .iter()
+            .flat_map(|&(first, last)| (first..=last).filter(|&v| is_invalid_id_part2(v)))
+            .sum();
 
-    result
+        Ok(result.into())
+    }
 }
 
 #[cfg(test)]
@@ -87,13 +97,29 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        let data = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        assert_eq!(part1(data), 1227775554);
+        assert_eq!(
+            Day2::part1(&Day2::parse(Day2::EXAMPLE).unwrap()).unwrap(),
+            1227775554
+        );
+    }
+
+    #[test]
+    fn parse_reports_location() {
+        let error = Day2::parse("11-22,95115\n").err().unwrap();
+        assert_eq!(
+            error,
+            ParseError::new(1, 7, "95115", "expected a range like 11-22")
+        );
+
+        let error = Day2::parse("11-22,\n95-1a5").err().unwrap();
+        assert_eq!(error, ParseError::new(2, 4, "1a5", "invalid number"));
     }
 
     #[test]
     fn part2_works() {
-        let data = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        assert_eq!(part2(data), 4174379265);
+        assert_eq!(
+            Day2::part2(&Day2::parse(Day2::EXAMPLE).unwrap()).unwrap(),
+            4174379265
+        );
     }
 }
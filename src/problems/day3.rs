@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-struct Bank {
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub struct Bank {
     #[allow(dead_code)]
     batteries: HashMap<u32, Vec<u32>>,
 }
@@ -18,7 +22,7 @@ fn largest_joltage_helper(
                     .iter()
                     .filter(|&&v| v as i64 > position && v < line_length as u32 - batteries_left)
                     .collect();
-                if possibilities.len() > 0 {
+                if !possibilities.is_empty() {
                     Some(**possibilities.first().unwrap())
                 } else {
                     None
@@ -27,11 +31,8 @@ fn largest_joltage_helper(
             None => None,
         };
 
-        match result {
-            Some(pos) => {
-                return Some((pos as i64, digit));
-            }
-            None => (),
+        if let Some(pos) = result {
+            return Some((pos as i64, digit));
         }
     }
     None
@@ -64,78 +65,94 @@ fn largest_joltage(bank: &Bank, batteries: u32, line_length: i64) -> Vec<u32> {
     let mut digits: Vec<u32> = Vec::new();
 
     for i in 0..batteries {
-        match largest_joltage_helper(bank, position, batteries - i - 1, line_length) {
-            Some((new_pos, digit)) => {
-                digits.push(digit);
-                position = new_pos;
-            }
-            None => (),
+        if let Some((new_pos, digit)) =
+            largest_joltage_helper(bank, position, batteries - i - 1, line_length)
+        {
+            digits.push(digit);
+            position = new_pos;
         };
     }
 
     digits
 }
 
-fn parse(data: &str) -> (Vec<Bank>, i64) {
-    let mut line_length: i64 = 0;
-    let banks: Vec<Bank> = data
-        .trim()
-        .split("\n")
+pub struct Day3;
+
+fn parse(data: &str) -> Result<(Vec<Bank>, i64), ParseError> {
+    let mut line_length: Option<i64> = None;
+    let banks = parse::lines(data)
         .map(|line| {
             let mut batteries: HashMap<u32, Vec<u32>> = HashMap::new();
-            line_length = line.len() as i64;
-            for (pos, _battery) in line
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as u32)
-                .enumerate()
-            {
-                if batteries.contains_key(&_battery) {
-                    batteries.get_mut(&_battery).unwrap().push(pos as u32);
-                } else {
-                    batteries.insert(_battery, vec![pos as u32]);
-                };
+            let digits = parse::digits(data, line)?;
+            if *line_length.get_or_insert(digits.len() as i64) != digits.len() as i64 {
+                return Err(ParseError::at(data, line, "rows must be the same length"));
+            }
+            for (pos, battery) in digits.into_iter().enumerate() {
+                batteries.entry(battery).or_default().push(pos as u32);
             }
 
-            Bank { batteries }
+            Ok(Bank { batteries })
         })
-        .collect();
-    (banks, line_length)
+        .collect::<Result<Vec<Bank>, ParseError>>()?;
+    Ok((banks, line_length.unwrap_or(0)))
 }
 
-pub fn part1(data: &str) -> i64 {
-    let (banks, line_length) = parse(data);
+/// Fails if the banks have fewer than `batteries` batteries to turn on.
+fn check_length(banks: &[Bank], line_length: i64, batteries: u32) -> Result<(), String> {
+    if !banks.is_empty() && line_length < batteries as i64 {
+        return Err(format!(
+            "banks have {} batteries, fewer than the {} needed",
+            line_length, batteries
+        ));
+    }
+    Ok(())
+}
 
-    let result: i64 = banks
-        .iter()
-        .map(|bank| {
-            let digits = largest_joltage(bank, 2, line_length);
-            let mut sum: u32 = 0;
-            for digit in &digits {
-                sum = sum * 10 + digit;
-            }
-            sum as i64
-        })
-        .sum();
+impl Solution for Day3 {
+    type Input = (Vec<Bank>, i64);
 
-    result
-}
+    const EXAMPLE: &'static str = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
 
-pub fn part2(data: &str) -> i64 {
-    let (banks, line_length) = parse(data);
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
 
-    let result: i64 = banks
-        .iter()
-        .map(|bank| {
-            let digits = largest_joltage(bank, 12, line_length);
-            let mut sum: i64 = 0;
-            for digit in &digits {
-                sum = sum * 10 + (*digit as i64);
-            }
-            sum
-        })
-        .sum();
+    fn part1((banks, line_length): &Self::Input) -> Result<Answer, String> {
+        check_length(banks, *line_length, 2)?;
+        let result: u32 = banks
+            .iter()
+            .map(|bank| {
+                let digits = largest_joltage(bank, 2, *line_length);
+                let mut sum: u32 = 0;
+                for digit in &digits {
+                    sum = sum * 10 + digit;
+                }
+                sum
+            })
+            .sum();
 
-    result
+        Ok(result.into())
+    }
+
+    fn part2((banks, line_length): &Self::Input) -> Result<Answer, String> {
+        check_length(banks, *line_length, 12)?;
+        let result: i64 = banks
+            .iter()
+            .map(|bank| {
+                let digits = largest_joltage(bank, 12, *line_length);
+                let mut sum: i64 = 0;
+                for digit in &digits {
+                    sum = sum * 10 + (*digit as i64);
+                }
+                sum
+            })
+            .sum();
+
+        Ok(result.into())
+    }
 }
 
 #[cfg(test)]
@@ -144,19 +161,43 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        let data = "987654321111111
-811111111111119
-234234234234278
-818181911112111";
-        assert_eq!(part1(data), 357);
+        assert_eq!(
+            Day3::part1(&Day3::parse(Day3::EXAMPLE).unwrap()).unwrap(),
+            357
+        );
+    }
+
+    #[test]
+    fn parse_reports_location() {
+        let error = Day3::parse("987654321111111\n81111x111111119")
+            .err()
+            .unwrap();
+        assert_eq!(error, ParseError::new(2, 6, "x", "expected a digit"));
+
+        let error = Day3::parse("98765432111\n123456789012345").err().unwrap();
+        assert_eq!(
+            error,
+            ParseError::new(2, 1, "123456789012345", "rows must be the same length")
+        );
+    }
+
+    #[test]
+    fn short_banks_are_errors() {
+        let banks = Day3::parse("12\n34").unwrap();
+        assert_eq!(Day3::part1(&banks).unwrap(), 46);
+        assert_eq!(
+            Day3::part2(&banks),
+            Err("banks have 2 batteries, fewer than the 12 needed".to_string())
+        );
+        assert!(Day3::part1(&Day3::parse("1").unwrap()).is_err());
+        assert_eq!(Day3::part2(&Day3::parse("").unwrap()).unwrap(), 0);
     }
 
     #[test]
     fn part2_works() {
-        let data = "987654321111111
-811111111111119
-234234234234278
-818181911112111";
-        assert_eq!(part2(data), 3121910778619);
+        assert_eq!(
+            Day3::part2(&Day3::parse(Day3::EXAMPLE).unwrap()).unwrap(),
+            3121910778619
+        );
     }
 }
@@ -1,105 +1,120 @@
-use std::collections::{HashMap, HashSet};
-
-use aoc2025::{GRID_DIRECTIONS, Grid, Position};
+use crate::answer::Answer;
+use crate::automaton::{Automaton, Update};
+use crate::parse::ParseError;
+use crate::solution::Solution;
+use crate::{Cell, Grid, Neighbourhood, Position, SparseGrid};
+
+pub struct Day4;
+
+fn parse(data: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::from_str_with(data, |c| match c {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
 
-fn parse(data: &str) -> Grid<bool> {
-    Grid::new(
-        data.trim()
-            .split("\n")
-            .map(|line| line.chars().map(|c| c == '@').collect())
-            .collect(),
-    )
+fn rolls(grid: &Grid<bool>) -> SparseGrid<bool> {
+    SparseGrid::from_grid(grid, |&roll| roll)
 }
 
-fn collect_adjacencies(grid: &Grid<bool>) -> HashMap<Position, HashSet<Position>> {
-    let mut res: HashMap<Position, HashSet<Position>> = HashMap::new();
+type Rule = fn(&Option<bool>, &[Cell<'_, bool>]) -> Option<bool>;
 
-    grid.into_iter().for_each(|cell| {
-        if cell.value {
-            res.insert(cell.position, HashSet::new());
-            grid.neighbours(&cell.position).iter().for_each(|nbr| {
-                if nbr.value {
-                    res.entry(cell.position).or_default().insert(nbr.position);
-                }
-            })
-        }
-    });
+/// A roll is removed once fewer than four rolls surround it.
+fn remaining(roll: &Option<bool>, neighbours: &[Cell<'_, bool>]) -> Option<bool> {
+    roll.filter(|_| neighbours.len() >= 4)
+}
 
-    res
+fn removal() -> Automaton<Rule> {
+    Automaton::new(Neighbourhood::Moore, Update::Synchronous, remaining)
 }
 
-pub fn neighbours(position: &Position) -> Vec<Position> {
-    let mut result = vec![];
-    for movement in GRID_DIRECTIONS {
-        result.push(position + movement)
-    }
+pub fn clean_up_rolls(rolls: &mut SparseGrid<bool>) -> usize {
+    removal().step(rolls)
+}
 
-    result
+/// The rolls removed in one generation, and optionally a picture of the grid
+/// afterwards with `x` marking the rolls just removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wave {
+    pub removed: Vec<Position>,
+    pub frame: Option<String>,
 }
 
-pub fn clean_up_rolls(adjacencies: &mut HashMap<Position, HashSet<Position>>) -> i64 {
-    let removed_rolls: Vec<Position> = adjacencies
+/// Removes rolls until none can be, one generation at a time. Each roll's
+/// neighbour count is kept up to date as rolls go, and a roll joins the next
+/// wave when its count drops below four, so every roll is handled at most
+/// once instead of rescanning the grid each generation.
+pub fn removal_waves(grid: &Grid<bool>, frames: bool) -> Vec<Wave> {
+    let mut counts: Grid<Option<usize>> = grid.map(|cell| {
+        cell.value.then(|| {
+            grid.neighbours(&cell.position)
+                .iter()
+                .filter(|neighbour| *neighbour.value)
+                .count()
+        })
+    });
+
+    let mut removed: Vec<Position> = counts
         .iter()
-        .filter(|(_, nbrs)| nbrs.len() < 4)
-        .map(|(roll, _)| *roll)
+        .filter(|cell| cell.value.is_some_and(|count| count < 4))
+        .map(|cell| cell.position)
         .collect();
 
-    for roll in &removed_rolls {
-        for (_, v) in adjacencies.iter_mut() {
-            v.remove(roll);
+    let mut waves = vec![];
+    while !removed.is_empty() {
+        for position in &removed {
+            *counts.get_mut(position).unwrap() = None;
         }
-    }
-
-    for roll in &removed_rolls {
-        adjacencies.remove(roll);
-    }
-
-    removed_rolls.len() as i64
-}
 
-pub fn print_grid(adjacencies: &HashMap<Position, HashSet<Position>>) {
-    for x in 0..137 {
-        for y in 0..137 {
-            if adjacencies.contains_key(&Position { x, y }) {
-                print!("@");
-            } else {
-                print!(".");
+        let mut next = vec![];
+        for position in &removed {
+            for neighbour in counts.neighbours_mut(position, &Neighbourhood::Moore) {
+                if let Some(count) = neighbour.value {
+                    *count -= 1;
+                    if *count == 3 {
+                        next.push(neighbour.position);
+                    }
+                }
             }
         }
 
-        print!("\n");
+        removed.sort_unstable();
+        let frame = frames.then(|| frame(&counts, &removed));
+        waves.push(Wave { removed, frame });
+        removed = next;
     }
-}
-
-pub fn part1(data: &str) -> i64 {
-    let grid = parse(data);
-    let mut adjacencies = collect_adjacencies(&grid);
 
-    clean_up_rolls(&mut adjacencies)
+    waves
 }
 
-pub fn part2(data: &str) -> i64 {
-    let grid = parse(data);
-    let mut adjacencies = collect_adjacencies(&grid);
-    let start_count = adjacencies.len();
+fn frame(counts: &Grid<Option<usize>>, removed: &[Position]) -> String {
+    counts.render(|cell| match cell.value {
+        Some(_) => '@',
+        None if removed.binary_search(&cell.position).is_ok() => 'x',
+        None => '.',
+    })
+}
 
-    loop {
-        let removed_rolls = clean_up_rolls(&mut adjacencies);
+/// The total number of rolls `removal_waves` removes.
+pub fn remove_all(grid: &Grid<bool>) -> usize {
+    removal_waves(grid, false)
+        .iter()
+        .map(|wave| wave.removed.len())
+        .sum()
+}
 
-        if removed_rolls == 0 {
-            break;
-        }
-    }
-    (start_count - adjacencies.len()) as i64
+pub fn print_grid(grid: &Grid<bool>) {
+    println!(
+        "{}",
+        grid.render(|cell| if *cell.value { '@' } else { '.' })
+    );
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Day4 {
+    type Input = Grid<bool>;
 
-    #[test]
-    fn part1_works() {
-        let data = "..@@.@@@@.
+    const EXAMPLE: &'static str = "..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
 @.@@@@..@.
@@ -109,21 +124,79 @@ mod tests {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@.";
-        assert_eq!(part1(data), 13);
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<Answer, String> {
+        let mut rolls = rolls(grid);
+
+        Ok(clean_up_rolls(&mut rolls).into())
+    }
+
+    fn part2(grid: &Self::Input) -> Result<Answer, String> {
+        Ok(remove_all(grid).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(
+            Day4::part1(&Day4::parse(Day4::EXAMPLE).unwrap()).unwrap(),
+            13
+        );
+    }
+
+    #[test]
+    fn parse_reports_location() {
+        let error = Day4::parse("..@@\n@x@.").err().unwrap();
+        assert_eq!(error, ParseError::new(2, 2, "x", "unexpected character"));
+
+        let error = Day4::parse("..@@\n@@.\n").err().unwrap();
+        assert_eq!(
+            error,
+            ParseError::new(2, 1, "@@.", "rows must be the same length")
+        );
     }
 
     #[test]
     fn part2_works() {
-        let data = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@.";
-        assert_eq!(part2(data), 43);
+        assert_eq!(
+            Day4::part2(&Day4::parse(Day4::EXAMPLE).unwrap()).unwrap(),
+            43
+        );
+    }
+
+    #[test]
+    fn remove_all_matches_generations() {
+        let grid = Day4::parse(Day4::EXAMPLE).unwrap();
+        let mut rolls = rolls(&grid);
+        let generations = removal().run_to_fixed_point(&mut rolls);
+        let waves: Vec<usize> = removal_waves(&grid, false)
+            .iter()
+            .map(|wave| wave.removed.len())
+            .collect();
+
+        assert_eq!(waves, generations);
+        assert_eq!(remove_all(&grid), generations.iter().sum());
+    }
+
+    #[test]
+    fn removal_waves_works() {
+        let grid = Day4::parse(Day4::EXAMPLE).unwrap();
+        let waves = removal_waves(&grid, true);
+
+        let sizes: Vec<usize> = waves.iter().map(|wave| wave.removed.len()).collect();
+        assert_eq!(sizes, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+
+        let first = waves[0].frame.as_ref().unwrap();
+        assert_eq!(first.lines().next(), Some("..xx.xx@x."));
+        assert_eq!(first.matches('x').count(), 13);
+        assert!(removal_waves(&grid, false)[0].frame.is_none());
     }
 }
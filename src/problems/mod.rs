@@ -1,4 +1,51 @@
+use crate::solution::Day;
+
 pub mod day1;
 pub mod day2;
 pub mod day3;
 pub mod day4;
+
+/// Every implemented day, in order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+];
+
+pub fn lookup(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_sorted_and_unique() {
+        assert!(DAYS.windows(2).all(|w| w[0].number < w[1].number));
+    }
+
+    #[test]
+    fn lookup_works() {
+        assert_eq!(lookup(4).unwrap().number, 4);
+        assert!(lookup(25).is_none());
+    }
+
+    #[test]
+    fn every_day_solves_its_example() {
+        for day in DAYS {
+            let parsed = (day.parse)(day.example)
+                .unwrap_or_else(|e| panic!("Day {} example: {}", day.number, e));
+            for (part, answer) in [(1, parsed.part1()), (2, parsed.part2())] {
+                assert!(
+                    answer.is_ok(),
+                    "Day {} part {}: {:?}",
+                    day.number,
+                    part,
+                    answer
+                );
+            }
+        }
+    }
+}
@@ -1,22 +1,29 @@
-fn parse(data: &str) -> Vec<&str> {
-    data
-        .trim()
-        .split("\n")
-        .collect()
-}
+use crate::answer::Answer;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+
+pub struct DayN;
 
-pub fn part1(data: &str) -> i64 {
-    let x = parse(data);
-    
-    let result: i64 = x.len() as i64;
-    result
+fn parse(data: &str) -> Result<Vec<String>, ParseError> {
+    Ok(parse::lines(data).map(String::from).collect())
 }
 
-pub fn part2(data: &str) -> i64 {
-    let x = parse(data);
-    
-    let result: i64 = x.len() as i64;
-    result
+impl Solution for DayN {
+    type Input = Vec<String>;
+
+    const EXAMPLE: &'static str = "";
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Answer, String> {
+        Ok(lines.len().into())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Answer, String> {
+        Ok(lines.len().into())
+    }
 }
 
 #[cfg(test)]
@@ -25,13 +32,11 @@ mod tests {
 
     #[test]
     fn part1_works() {
-        let data = "";
-        assert_eq!(part1(data), 0);
+        assert_eq!(DayN::part1(&DayN::parse(DayN::EXAMPLE).unwrap()).unwrap(), 0);
     }
 
     #[test]
     fn part2_works() {
-        let data = "";
-        assert_eq!(part2(data), 0);
+        assert_eq!(DayN::part2(&DayN::parse(DayN::EXAMPLE).unwrap()).unwrap(), 0);
     }
 }
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use crate::search;
+use crate::{Cell, Direction, Grid, Neighbourhood, Position};
+
+/// A connected group of grid cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The first cell of the region in row-major order, or the cell a flood
+    /// fill started from.
+    pub start: Position,
+    pub cells: HashSet<Position>,
+}
+
+impl Region {
+    pub fn contains(&self, position: &Position) -> bool {
+        self.cells.contains(position)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges between the region and the outside,
+    /// including the edges of any holes.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|cell| Neighbourhood::Orthogonal.around(cell))
+            .filter(|neighbour| !self.contains(neighbour))
+            .count()
+    }
+
+    /// The number of straight sides of the region's outline, including the
+    /// outlines of holes. This is the same as its number of corners.
+    pub fn sides(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| {
+                Direction::iterator()
+                    .filter(|direction| {
+                        let a = direction.advance_by();
+                        let b = direction.turn().advance_by();
+                        let inside = |offset: Position| self.contains(&(cell + offset));
+                        let outer = !inside(a) && !inside(b);
+                        let inner = inside(a) && inside(b) && !inside(a + b);
+                        outer || inner
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The smallest and largest corners of the box containing the region.
+    pub fn bounds(&self) -> (Position, Position) {
+        let mut min = self.start;
+        let mut max = self.start;
+        for cell in &self.cells {
+            min = Position {
+                x: min.x.min(cell.x),
+                y: min.y.min(cell.y),
+            };
+            max = Position {
+                x: max.x.max(cell.x),
+                y: max.y.max(cell.y),
+            };
+        }
+        (min, max)
+    }
+}
+
+impl<T> Grid<T> {
+    /// The region around `start` whose cells are connected through
+    /// `neighbourhood` and for which `same(start value, cell value)` holds.
+    /// The region is empty if `start` is outside the grid.
+    pub fn flood_fill(
+        &self,
+        start: Position,
+        neighbourhood: &Neighbourhood,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> Region {
+        let Some(seed) = self.get(&start) else {
+            return Region {
+                start,
+                cells: HashSet::new(),
+            };
+        };
+
+        self.fill(start, neighbourhood, |cell| same(seed, cell.value))
+    }
+
+    /// The cells connected to `start` through `neighbourhood` for which
+    /// `include` holds, along with `start` itself.
+    fn fill(
+        &self,
+        start: Position,
+        neighbourhood: &Neighbourhood,
+        include: impl Fn(Cell<'_, T>) -> bool,
+    ) -> Region {
+        let reached = search::bfs(start, |position| {
+            self.neighbours_in(position, neighbourhood)
+                .into_iter()
+                .filter(|&neighbour| include(neighbour))
+                .map(|neighbour| neighbour.position)
+                .collect::<Vec<_>>()
+        });
+
+        Region {
+            start,
+            cells: reached.distances.into_keys().collect(),
+        }
+    }
+
+    /// Splits the grid into regions as `flood_fill` does, in row-major order
+    /// of their first cells, along with a grid labelling each cell with the
+    /// index of its region. A region only takes cells no earlier region has,
+    /// so each cell is in exactly one even if `same` isn't symmetric.
+    pub fn regions(
+        &self,
+        neighbourhood: &Neighbourhood,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> (Vec<Region>, Grid<usize>) {
+        let mut labels: Vec<Option<usize>> = vec![None; self.cells.len()];
+        let mut regions = vec![];
+
+        for index in 0..self.cells.len() {
+            if labels[index].is_some() {
+                continue;
+            }
+
+            let seed = &self.cells[index];
+            let region = self.fill(self.position(index), neighbourhood, |cell| {
+                labels[self.index(&cell.position).unwrap()].is_none() && same(seed, cell.value)
+            });
+            for cell in &region.cells {
+                labels[self.index(cell).unwrap()] = Some(regions.len());
+            }
+            regions.push(region);
+        }
+
+        let labels = Grid {
+            cells: labels.into_iter().map(Option::unwrap).collect(),
+            width: self.width,
+            height: self.height,
+        };
+        (regions, labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(a: &char, b: &char) -> bool {
+        a == b
+    }
+
+    #[test]
+    fn region_measurements() {
+        let grid = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let (regions, labels) = grid.regions(&Neighbourhood::Orthogonal, same);
+
+        let measured: Vec<(char, usize, usize, usize)> = regions
+            .iter()
+            .map(|region| {
+                (
+                    *grid.get(&region.start).unwrap(),
+                    region.area(),
+                    region.perimeter(),
+                    region.sides(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            measured,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+
+        assert_eq!(labels.get(&Position { x: 3, y: 3 }), Some(&2));
+        assert_eq!(
+            regions[2].bounds(),
+            (Position { x: 1, y: 2 }, Position { x: 3, y: 3 })
+        );
+    }
+
+    #[test]
+    fn region_with_holes() {
+        let grid = Grid::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").unwrap();
+        let region = grid.flood_fill(Position { x: 0, y: 0 }, &Neighbourhood::Orthogonal, same);
+
+        assert_eq!(region.area(), 21);
+        assert_eq!(region.perimeter(), 36);
+        assert_eq!(region.sides(), 20);
+    }
+
+    #[test]
+    fn flood_fill_neighbourhoods() {
+        let grid = Grid::parse("@.@\n.@.\n..@").unwrap();
+        let start = Position { x: 0, y: 0 };
+
+        let region = grid.flood_fill(start, &Neighbourhood::Orthogonal, same);
+        assert_eq!(region.area(), 1);
+
+        let region = grid.flood_fill(start, &Neighbourhood::Moore, same);
+        assert_eq!(region.area(), 4);
+        assert!(region.contains(&Position { x: 2, y: 2 }));
+
+        let (regions, _) = grid.regions(&Neighbourhood::Moore, same);
+        assert_eq!(regions.len(), 2);
+
+        let outside = grid.flood_fill(Position { x: 3, y: 0 }, &Neighbourhood::Moore, same);
+        assert_eq!(outside.area(), 0);
+    }
+
+    #[test]
+    fn regions_with_asymmetric_same() {
+        // The 1's region would take in the 2, but the 2 already has a
+        // region of its own, so it mustn't be claimed twice.
+        let grid = Grid::new(vec![vec![2, 1]]);
+        let (regions, labels) = grid.regions(&Neighbourhood::Orthogonal, |a: &u32, b| a <= b);
+
+        let areas: Vec<usize> = regions.iter().map(Region::area).collect();
+        assert_eq!(areas, vec![1, 1]);
+        let labels: Vec<usize> = labels.iter().map(|cell| *cell.value).collect();
+        assert_eq!(labels, vec![0, 1]);
+    }
+}
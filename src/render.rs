@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use crate::{Cell, Grid, Position};
+
+/// A foreground colour for terminal output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Black = 30,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+/// Reverse video, used to pick out highlighted cells.
+const HIGHLIGHT: &str = "7";
+
+impl<T> Grid<T> {
+    /// Draws the grid one row per line, mapping each cell with `cell`.
+    pub fn render(&self, cell: impl Fn(Cell<'_, T>) -> char) -> String {
+        self.render_coloured(|c| (cell(c), None), &[])
+    }
+
+    /// Like `render`, but `cell` can also give a colour, and the cells in
+    /// `highlighted` (a path, say) are shown in reverse video. Colours use
+    /// ANSI escape codes, so the result is meant for a terminal.
+    pub fn render_coloured(
+        &self,
+        cell: impl Fn(Cell<'_, T>) -> (char, Option<Colour>),
+        highlighted: &[Position],
+    ) -> String {
+        let highlighted: HashSet<&Position> = highlighted.iter().collect();
+        let size = self.size();
+        let mut result = String::with_capacity((size.x * (size.y + 1)) as usize);
+
+        for c in self {
+            if c.position.y == 0 && c.position.x > 0 {
+                result.push('\n');
+            }
+
+            let (symbol, colour) = cell(c);
+            let codes: Vec<String> = colour
+                .map(|colour| (colour as u8).to_string())
+                .into_iter()
+                .chain(
+                    highlighted
+                        .contains(&c.position)
+                        .then(|| HIGHLIGHT.to_string()),
+                )
+                .collect();
+            if codes.is_empty() {
+                result.push(symbol);
+            } else {
+                result.push_str(&format!("\x1b[{}m{}\x1b[0m", codes.join(";"), symbol));
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_plain() {
+        let grid = Grid::new(vec![vec![true, false], vec![false, true]]);
+        let rendered = grid.render(|cell| if *cell.value { '@' } else { '.' });
+        assert_eq!(rendered, "@.\n.@");
+        assert_eq!(Grid::<bool>::new(vec![]).render(|_| '@'), "");
+    }
+
+    #[test]
+    fn render_colours_and_highlights() {
+        let grid = Grid::parse("#.\n.#").unwrap();
+        let rendered = grid.render_coloured(
+            |cell| match cell.value {
+                '#' => ('#', Some(Colour::Red)),
+                c => (*c, None),
+            },
+            &[Position { x: 0, y: 1 }, Position { x: 1, y: 1 }],
+        );
+
+        assert_eq!(
+            rendered,
+            "\x1b[31m#\x1b[0m\x1b[7m.\x1b[0m\n.\x1b[31;7m#\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::answers::{Check, Recorded};
+use crate::parse::ParseError;
+use crate::solution::Day;
+
+/// Why a day could not be run: its input didn't parse, or a part gave no
+/// answer.
+#[derive(Debug)]
+pub enum RunError {
+    Parse(ParseError),
+    Part { part: u32, message: String },
+}
+
+pub struct PartReport {
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub check: Check,
+}
+
+pub struct DayReport {
+    pub day: u32,
+    pub parse: Duration,
+    pub part1: PartReport,
+    pub part2: PartReport,
+}
+
+impl DayReport {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.elapsed + self.part2.elapsed
+    }
+
+    pub fn failed(&self) -> bool {
+        [&self.part1, &self.part2]
+            .iter()
+            .any(|part| matches!(part.check, Check::Fail { .. }))
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs parse, part 1 and part 2 of a day, timing each phase separately and
+/// checking both answers against the recorded ones.
+pub fn run(day: &Day, data: &str, recorded: &Recorded) -> Result<DayReport, RunError> {
+    let (parsed, parse) = timed(|| (day.parse)(data));
+    let parsed = parsed.map_err(RunError::Parse)?;
+    let (answer1, elapsed1) = timed(|| parsed.part1());
+    let (answer2, elapsed2) = timed(|| parsed.part2());
+    let answer1 = answer1.map_err(|message| RunError::Part { part: 1, message })?;
+    let answer2 = answer2.map_err(|message| RunError::Part { part: 2, message })?;
+
+    Ok(DayReport {
+        day: day.number,
+        parse,
+        part1: PartReport {
+            check: recorded.check(1, &answer1),
+            answer: answer1,
+            elapsed: elapsed1,
+        },
+        part2: PartReport {
+            check: recorded.check(2, &answer2),
+            answer: answer2,
+            elapsed: elapsed2,
+        },
+    })
+}
+
+fn summarise(part: &PartReport) -> String {
+    let text = part.answer.to_string();
+    let text = match text.split_once('\n') {
+        Some((first, _)) => format!("{}...", first),
+        None => text,
+    };
+    format!("{} {}", text, part.check)
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+/// Formats the reports as an aligned table with a total row.
+pub fn summary_table(reports: &[DayReport]) -> String {
+    let header = [
+        "Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2", "Total",
+    ]
+    .map(String::from);
+    let mut rows: Vec<[String; 7]> = reports
+        .iter()
+        .map(|report| {
+            [
+                report.day.to_string(),
+                summarise(&report.part1),
+                summarise(&report.part2),
+                format_duration(report.parse),
+                format_duration(report.part1.elapsed),
+                format_duration(report.part2.elapsed),
+                format_duration(report.total()),
+            ]
+        })
+        .collect();
+
+    let sum = |f: fn(&DayReport) -> Duration| reports.iter().map(f).sum::<Duration>();
+    rows.push([
+        "Total".to_string(),
+        String::new(),
+        String::new(),
+        format_duration(sum(|r| r.parse)),
+        format_duration(sum(|r| r.part1.elapsed)),
+        format_duration(sum(|r| r.part2.elapsed)),
+        format_duration(sum(|r| r.total())),
+    ]);
+
+    let mut widths = header.clone().map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String; 7]| {
+        row.iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                // Day numbers and answers read left to right, timings are right aligned.
+                if i < 3 {
+                    format!("{:<width$}", cell)
+                } else {
+                    format!("{:>width$}", cell)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+
+    let separator = widths.map(|width| "-".repeat(width)).join("-+-");
+
+    let mut lines = vec![format_row(&header), separator.clone()];
+    let total = rows.pop().unwrap();
+    lines.extend(rows.iter().map(format_row));
+    lines.push(separator);
+    lines.push(format_row(&total));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(day: u32, part1: Answer, millis: u64) -> DayReport {
+        DayReport {
+            day,
+            parse: Duration::from_millis(millis),
+            part1: PartReport {
+                answer: part1,
+                elapsed: Duration::from_millis(millis),
+                check: Check::Pass,
+            },
+            part2: PartReport {
+                answer: Answer::from("#..#\n####"),
+                elapsed: Duration::from_millis(millis),
+                check: Check::New,
+            },
+        }
+    }
+
+    #[test]
+    fn run_times_every_phase() {
+        let recorded = Recorded::parse("part1 = 2\npart2 = 3").unwrap();
+        let report = run(crate::problems::lookup(1).unwrap(), "L50\nR100", &recorded).unwrap();
+        assert_eq!(report.day, 1);
+        assert_eq!(report.part1.answer, 2);
+        assert_eq!(report.part2.answer, 2);
+        assert_eq!(report.part1.check, Check::Pass);
+        assert!(report.failed());
+        assert_eq!(
+            report.total(),
+            report.parse + report.part1.elapsed + report.part2.elapsed
+        );
+    }
+
+    #[test]
+    fn summary_table_is_aligned() {
+        let table = summary_table(&[
+            report(1, Answer::from(3), 1),
+            report(12, Answer::from(123456), 2),
+        ]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        assert!(lines[2].starts_with("1     | 3 PASS      | #..#... NEW"));
+        assert!(lines[5].starts_with("Total |"));
+        assert!(lines[5].ends_with("9.00ms"));
+    }
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::Path;
+
+const TEMPLATE_NAME: &str = "DayN";
+
+fn module_line(day: u32) -> String {
+    format!("pub mod day{};", day)
+}
+
+fn registry_line(day: u32) -> String {
+    format!("    Day::new::<day{0}::Day{0}>({0}),", day)
+}
+
+fn day_of(line: &str, prefix: &str) -> Option<u32> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Inserts `line` among the lines starting with `prefix`, keeping them
+/// ordered by day. Returns false if the day is already present.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    prefix: &str,
+    day: u32,
+    line: String,
+) -> Result<bool, String> {
+    let existing: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| day_of(l, prefix).map(|d| (i, d)))
+        .collect();
+
+    if existing.iter().any(|&(_, d)| d == day) {
+        return Ok(false);
+    }
+
+    let index = match existing.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => i,
+        None => existing
+            .last()
+            .map(|&(i, _)| i + 1)
+            .ok_or(format!("No `{}` lines to insert next to", prefix))?,
+    };
+    lines.insert(index, line);
+    Ok(true)
+}
+
+/// Adds the module declaration and registry entry for a day to the contents
+/// of `problems/mod.rs`. Already registered days are left as they are.
+pub fn register(mod_rs: &str, day: u32) -> Result<String, String> {
+    let mut lines: Vec<String> = mod_rs.lines().map(String::from).collect();
+    insert_sorted(&mut lines, "pub mod day", day, module_line(day))?;
+    insert_sorted(&mut lines, "Day::new::<day", day, registry_line(day))?;
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Ok(result)
+}
+
+/// Creates the module for a new day from the template, registers it and
+/// creates its (empty) input file. Existing files are never overwritten, so
+/// running this twice is harmless. Returns a description of each step.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<String>, String> {
+    let mut steps = vec![];
+    let problems = root.join("src/problems");
+
+    let module = problems.join(format!("day{}.rs", day));
+    if module.exists() {
+        steps.push(format!(
+            "{} already exists, leaving it untouched",
+            module.display()
+        ));
+    } else {
+        let template = fs::read_to_string(problems.join("template.rs"))
+            .map_err(|e| format!("Failed to read template: {}", e))?;
+        fs::write(
+            &module,
+            template.replace(TEMPLATE_NAME, &format!("Day{}", day)),
+        )
+        .map_err(|e| format!("Failed to write {}: {}", module.display(), e))?;
+        steps.push(format!("Created {}", module.display()));
+    }
+
+    let mod_rs = problems.join("mod.rs");
+    let contents = fs::read_to_string(&mod_rs)
+        .map_err(|e| format!("Failed to read {}: {}", mod_rs.display(), e))?;
+    let registered = register(&contents, day)?;
+    if registered == contents {
+        steps.push(format!("Day {} is already registered", day));
+    } else {
+        fs::write(&mod_rs, registered)
+            .map_err(|e| format!("Failed to write {}: {}", mod_rs.display(), e))?;
+        steps.push(format!("Registered day {} in {}", day, mod_rs.display()));
+    }
+
+    let data = root.join("data");
+    let input = data.join(format!("day{}.data", day));
+    if !input.exists() {
+        fs::create_dir_all(&data)
+            .map_err(|e| format!("Failed to create {}: {}", data.display(), e))?;
+        fs::write(&input, "").map_err(|e| format!("Failed to write {}: {}", input.display(), e))?;
+        steps.push(format!("Created {}", input.display()));
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "use crate::solution::Day;
+
+pub mod day1;
+pub mod day3;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day3::Day3>(3),
+];
+";
+
+    #[test]
+    fn register_appends_day() {
+        let result = register(MOD_RS, 4).unwrap();
+        assert!(result.contains("pub mod day3;\npub mod day4;\n\n"));
+        assert!(result.contains("(3),\n    Day::new::<day4::Day4>(4),\n];"));
+    }
+
+    #[test]
+    fn register_keeps_days_ordered() {
+        let result = register(MOD_RS, 2).unwrap();
+        assert!(result.contains("pub mod day1;\npub mod day2;\npub mod day3;"));
+        assert!(result.contains("(1),\n    Day::new::<day2::Day2>(2),\n    Day::new::<day3"));
+    }
+
+    #[test]
+    fn register_is_idempotent() {
+        assert_eq!(register(MOD_RS, 3).unwrap(), MOD_RS);
+        let once = register(MOD_RS, 12).unwrap();
+        assert_eq!(register(&once, 12).unwrap(), once);
+    }
+
+    #[test]
+    fn new_day_does_not_clobber() {
+        let root = std::env::temp_dir().join(format!("aoc2025-scaffold-{}", std::process::id()));
+        let problems = root.join("src/problems");
+        fs::create_dir_all(&problems).unwrap();
+        fs::write(problems.join("mod.rs"), MOD_RS).unwrap();
+        fs::write(problems.join("template.rs"), "pub struct DayN;\n").unwrap();
+
+        new_day(&root, 5).unwrap();
+        assert_eq!(
+            fs::read_to_string(problems.join("day5.rs")).unwrap(),
+            "pub struct Day5;\n"
+        );
+        assert!(root.join("data/day5.data").exists());
+
+        fs::write(problems.join("day5.rs"), "solved").unwrap();
+        let mod_rs = fs::read_to_string(problems.join("mod.rs")).unwrap();
+        new_day(&root, 5).unwrap();
+        assert_eq!(
+            fs::read_to_string(problems.join("day5.rs")).unwrap(),
+            "solved"
+        );
+        assert_eq!(fs::read_to_string(problems.join("mod.rs")).unwrap(), mod_rs);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,534 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::{Cell, Grid, Neighbourhood, Position};
+
+/// The result of a search: the cost of reaching each visited state and the
+/// state it was reached from on a cheapest path.
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+    pub start: S,
+    pub distances: HashMap<S, u64>,
+    pub predecessors: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash> Paths<S> {
+    fn new(start: S) -> Self {
+        Paths {
+            distances: HashMap::from([(start.clone(), 0)]),
+            predecessors: HashMap::new(),
+            start,
+        }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    /// A cheapest path from the start to `target`, including both ends, or
+    /// `None` if `target` wasn't reached.
+    pub fn path(&self, target: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(target) {
+            return None;
+        }
+
+        let mut path = vec![target.clone()];
+        let mut state = target;
+        while let Some(previous) = self.predecessors.get(state) {
+            path.push(previous.clone());
+            state = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Like `Paths`, but keeping every predecessor on a cheapest path, so all
+/// the cheapest paths to a state can be counted or listed.
+#[derive(Debug, Clone)]
+pub struct AllPaths<S> {
+    pub start: S,
+    pub distances: HashMap<S, u64>,
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// The states in the order the search settled them. Every predecessor
+    /// comes before the states it leads to.
+    pub settled: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> AllPaths<S> {
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    fn predecessors_of(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every state on some cheapest path from the start to `target`.
+    pub fn on_paths(&self, target: &S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        if !self.distances.contains_key(target) {
+            return seen;
+        }
+
+        let mut stack = vec![target.clone()];
+        while let Some(state) = stack.pop() {
+            if seen.insert(state.clone()) {
+                stack.extend(self.predecessors_of(&state).iter().cloned());
+            }
+        }
+        seen
+    }
+
+    /// The number of distinct cheapest paths from the start to `target`.
+    pub fn count(&self, target: &S) -> u64 {
+        let on_paths = self.on_paths(target);
+
+        let mut counts: HashMap<&S, u64> = HashMap::new();
+        for state in self.settled.iter().filter(|state| on_paths.contains(state)) {
+            let count = if *state == self.start {
+                1
+            } else {
+                self.predecessors_of(state)
+                    .iter()
+                    .map(|previous| counts[previous])
+                    .sum()
+            };
+            counts.insert(state, count);
+        }
+        counts.get(target).copied().unwrap_or(0)
+    }
+
+    /// Every cheapest path from the start to `target`, each including both
+    /// ends. There can be exponentially many; see `count`.
+    pub fn paths(&self, target: &S) -> Vec<Vec<S>> {
+        let mut result = vec![];
+        if !self.distances.contains_key(target) {
+            return result;
+        }
+
+        let mut stack = vec![vec![target.clone()]];
+        while let Some(mut path) = stack.pop() {
+            let last = path.last().unwrap();
+            if *last == self.start {
+                path.reverse();
+                result.push(path);
+                continue;
+            }
+
+            for previous in self.predecessors_of(last) {
+                let mut longer = path.clone();
+                longer.push(previous.clone());
+                stack.push(longer);
+            }
+        }
+        result
+    }
+}
+
+/// A queued state, ordered so `BinaryHeap` pops the lowest priority first.
+struct Entry<S> {
+    priority: u64,
+    cost: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S> Eq for Entry<S> {}
+
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Visits every state reachable from `start`, where each step costs 1.
+pub fn bfs<S, I>(start: S, successors: impl Fn(&S) -> I) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, cost)) = queue.pop_front() {
+        for next in successors(&state) {
+            if paths.distances.contains_key(&next) {
+                continue;
+            }
+            paths.distances.insert(next.clone(), cost + 1);
+            paths.predecessors.insert(next.clone(), state.clone());
+            queue.push_back((next, cost + 1));
+        }
+    }
+
+    paths
+}
+
+/// Visits every state reachable from `start`, cheapest first.
+pub fn dijkstra<S, I>(start: S, successors: impl Fn(&S) -> I) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    explore(start, successors, |_| 0, |_| false).1
+}
+
+/// Searches from `start` until a state satisfying `goal` is reached, guided
+/// by `heuristic`, which must never overestimate the remaining cost. Returns
+/// the goal reached and the states explored on the way.
+pub fn astar<S, I>(
+    start: S,
+    successors: impl Fn(&S) -> I,
+    heuristic: impl Fn(&S) -> u64,
+    goal: impl Fn(&S) -> bool,
+) -> Option<(S, Paths<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let (reached, paths) = explore(start, successors, heuristic, goal);
+    reached.map(|state| (state, paths))
+}
+
+/// Like `dijkstra`, but records every cheapest way of reaching each state.
+/// A zero-cost step into a state that is already settled isn't recorded, so
+/// states at the same distance are never each other's predecessors and
+/// paths only take such steps in the order states were settled.
+pub fn dijkstra_all<S, I>(start: S, successors: impl Fn(&S) -> I) -> AllPaths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut paths = AllPaths {
+        distances: HashMap::from([(start.clone(), 0)]),
+        predecessors: HashMap::new(),
+        start: start.clone(),
+        settled: vec![],
+    };
+    let mut queue = BinaryHeap::from([Entry {
+        priority: 0,
+        cost: 0,
+        state: start,
+    }]);
+    let mut settled = HashSet::new();
+
+    while let Some(Entry { cost, state, .. }) = queue.pop() {
+        if paths.distance(&state).is_some_and(|best| best < cost) || settled.contains(&state) {
+            continue;
+        }
+        settled.insert(state.clone());
+        paths.settled.push(state.clone());
+
+        for (next, step) in successors(&state) {
+            if settled.contains(&next) {
+                continue;
+            }
+
+            let next_cost = cost + step;
+            match paths.distance(&next) {
+                Some(best) if best < next_cost => continue,
+                Some(best) if best == next_cost => {
+                    let previous = paths.predecessors.entry(next).or_default();
+                    if !previous.contains(&state) {
+                        previous.push(state.clone());
+                    }
+                }
+                _ => {
+                    paths.distances.insert(next.clone(), next_cost);
+                    paths.predecessors.insert(next.clone(), vec![state.clone()]);
+                    queue.push(Entry {
+                        priority: next_cost,
+                        cost: next_cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+fn explore<S, I>(
+    start: S,
+    successors: impl Fn(&S) -> I,
+    heuristic: impl Fn(&S) -> u64,
+    goal: impl Fn(&S) -> bool,
+) -> (Option<S>, Paths<S>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Entry { cost, state, .. }) = queue.pop() {
+        if paths.distance(&state).is_some_and(|best| best < cost) {
+            continue;
+        }
+        if goal(&state) {
+            return (Some(state), paths);
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if paths.distance(&next).is_some_and(|best| best <= next_cost) {
+                continue;
+            }
+            paths.distances.insert(next.clone(), next_cost);
+            paths.predecessors.insert(next.clone(), state.clone());
+            queue.push(Entry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    (None, paths)
+}
+
+impl<T> Grid<T> {
+    /// The orthogonal moves out of `position` that `cost` allows, with their
+    /// costs. `cost` gets the cell moved from and the cell moved to.
+    fn moves(
+        &self,
+        position: &Position,
+        cost: &impl Fn(Cell<'_, T>, Cell<'_, T>) -> Option<u64>,
+    ) -> Vec<(Position, u64)> {
+        let Some(value) = self.get(position) else {
+            return vec![];
+        };
+
+        let from = Cell {
+            value,
+            position: *position,
+        };
+        self.neighbours_in(position, &Neighbourhood::Orthogonal)
+            .into_iter()
+            .filter_map(|to| cost(from, to).map(|cost| (to.position, cost)))
+            .collect()
+    }
+
+    /// Step counts from `start` over orthogonal moves, where `passable` gets
+    /// the cell moved from and the cell moved to.
+    pub fn bfs(
+        &self,
+        start: Position,
+        passable: impl Fn(Cell<'_, T>, Cell<'_, T>) -> bool,
+    ) -> Paths<Position> {
+        let cost = |from: Cell<'_, T>, to: Cell<'_, T>| passable(from, to).then_some(1);
+        bfs(start, |position| {
+            self.moves(position, &cost)
+                .into_iter()
+                .map(|(position, _)| position)
+        })
+    }
+
+    /// Cheapest costs from `start` over orthogonal moves, where `cost` gives
+    /// the price of moving between two cells, or `None` if it isn't allowed.
+    pub fn dijkstra(
+        &self,
+        start: Position,
+        cost: impl Fn(Cell<'_, T>, Cell<'_, T>) -> Option<u64>,
+    ) -> Paths<Position> {
+        dijkstra(start, |position| self.moves(position, &cost))
+    }
+
+    /// Like `dijkstra`, but stops once `goal` is reached, using the Manhattan
+    /// distance as a heuristic. Every move must cost at least 1. Returns
+    /// `None` if `goal` can't be reached.
+    pub fn astar(
+        &self,
+        start: Position,
+        goal: Position,
+        cost: impl Fn(Cell<'_, T>, Cell<'_, T>) -> Option<u64>,
+    ) -> Option<Paths<Position>> {
+        astar(
+            start,
+            |position| self.moves(position, &cost),
+            |p| p.manhattan(&goal),
+            |p| *p == goal,
+        )
+        .map(|(_, paths)| paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    const MAZE: &str = "S..#
+.#.#
+.#..
+...E";
+
+    fn open(_: Cell<'_, char>, to: Cell<'_, char>) -> bool {
+        *to.value != '#'
+    }
+
+    #[test]
+    fn grid_bfs() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let paths = grid.bfs(Position { x: 0, y: 0 }, open);
+
+        let end = Position { x: 3, y: 3 };
+        assert_eq!(paths.distance(&end), Some(6));
+        assert_eq!(paths.distance(&Position { x: 0, y: 3 }), None);
+
+        let path = paths.path(&end).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path[0], Position { x: 0, y: 0 });
+        assert!(
+            path.windows(2)
+                .all(|step| { step[0].x.abs_diff(step[1].x) + step[0].y.abs_diff(step[1].y) == 1 })
+        );
+    }
+
+    #[test]
+    fn grid_dijkstra_and_astar_agree() {
+        let grid = Grid::from_str_with("1163\n1381\n2136", |c| c.to_digit(10)).unwrap();
+        let cost = |_: Cell<'_, u32>, to: Cell<'_, u32>| Some(*to.value as u64);
+
+        let start = Position { x: 0, y: 0 };
+        let end = Position { x: 2, y: 3 };
+        let paths = grid.dijkstra(start, cost);
+        assert_eq!(paths.distance(&end), Some(13));
+
+        let found = grid.astar(start, end, cost).unwrap();
+        assert_eq!(found.distance(&end), Some(13));
+        assert_eq!(found.path(&end), paths.path(&end));
+
+        let walled = |_: Cell<'_, u32>, to: Cell<'_, u32>| (*to.value != 8).then_some(1);
+        assert!(grid.astar(start, Position { x: 1, y: 2 }, walled).is_none());
+    }
+
+    #[test]
+    fn search_with_direction_state() {
+        // Moving forward costs 1 and turning costs 1000, so the cheapest route
+        // to the end avoids turns even though it's longer.
+        let grid = Grid::parse("....E\n.###.\nS....").unwrap();
+        let start = (Position { x: 2, y: 0 }, Direction::East);
+        let successors = |&(position, direction): &(Position, Direction)| {
+            let mut next = vec![
+                ((position, direction.turn()), 1000),
+                ((position, direction.turn().turn().turn()), 1000),
+            ];
+            let ahead = position + direction.advance_by();
+            if grid.get(&ahead).is_some_and(|c| *c != '#') {
+                next.push(((ahead, direction), 1));
+            }
+            next
+        };
+
+        let end = Position { x: 0, y: 4 };
+        let (reached, paths) = astar(
+            start,
+            successors,
+            |(p, _)| p.manhattan(&end),
+            |(p, _)| *p == end,
+        )
+        .unwrap();
+        assert_eq!(reached, (end, Direction::North));
+        assert_eq!(paths.distance(&reached), Some(1006));
+
+        let all = dijkstra(start, successors);
+        assert_eq!(all.distance(&reached), Some(1006));
+        assert_eq!(all.path(&reached).unwrap().len(), 8);
+    }
+
+    fn lattice(&position: &Position) -> Vec<(Position, u64)> {
+        [Position { x: 1, y: 0 }, Position { x: 0, y: 1 }]
+            .into_iter()
+            .map(|step| position + step)
+            .filter(|next| next.x <= 2 && next.y <= 2)
+            .map(|next| (next, 1))
+            .collect()
+    }
+
+    #[test]
+    fn dijkstra_all_counts_paths() {
+        let start = Position { x: 0, y: 0 };
+        let end = Position { x: 2, y: 2 };
+        let paths = dijkstra_all(start, lattice);
+
+        assert_eq!(paths.distance(&end), Some(4));
+        assert_eq!(paths.count(&end), 6);
+        assert_eq!(paths.count(&start), 1);
+        assert_eq!(paths.count(&Position { x: 3, y: 0 }), 0);
+
+        let mut all = paths.paths(&Position { x: 1, y: 1 });
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                vec![start, Position { x: 0, y: 1 }, Position { x: 1, y: 1 }],
+                vec![start, Position { x: 1, y: 0 }, Position { x: 1, y: 1 }],
+            ]
+        );
+        assert_eq!(paths.paths(&end).len(), 6);
+    }
+
+    #[test]
+    fn dijkstra_all_ignores_costlier_paths() {
+        // The direct edge to 3 is dearer than going through 1 or 2.
+        let successors = |&state: &u32| match state {
+            0 => vec![(1, 1), (2, 1), (3, 5)],
+            1 | 2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let paths = dijkstra_all(0, successors);
+
+        assert_eq!(paths.distance(&3), Some(2));
+        assert_eq!(paths.count(&3), 2);
+        assert_eq!(paths.on_paths(&3), HashSet::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn dijkstra_all_free_steps() {
+        // A teleport from 0 to 1 costs nothing.
+        let successors = |&state: &u32| match state {
+            0 => vec![(1, 0)],
+            1 => vec![(2, 1)],
+            _ => vec![],
+        };
+        let paths = dijkstra_all(0, successors);
+        assert_eq!(paths.distance(&2), Some(1));
+        assert_eq!(paths.count(&2), 1);
+        assert_eq!(paths.paths(&2), vec![vec![0, 1, 2]]);
+
+        // 1 and 2 are both 1 away, and free steps join them both ways. Only
+        // the step out of whichever was settled first is kept.
+        let successors = |&state: &u32| match state {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(2, 0)],
+            2 => vec![(1, 0)],
+            _ => vec![],
+        };
+        let paths = dijkstra_all(0, successors);
+        let (first, second) = (paths.settled[1], paths.settled[2]);
+        assert_eq!(paths.distance(&first), Some(1));
+        assert_eq!(paths.distance(&second), Some(1));
+        assert_eq!(paths.count(&first), 1);
+        assert_eq!(paths.count(&second), 2);
+        assert_eq!(paths.paths(&second).len(), 2);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::answer::Answer;
+use crate::parse::ParseError;
+
+/// A puzzle solution: the input is parsed once and both parts are answered
+/// from the parsed value. A part can fail with a message when the input
+/// parses but has no answer.
+pub trait Solution {
+    type Input: 'static;
+
+    /// The example input from the puzzle text.
+    const EXAMPLE: &'static str;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Answer, String>;
+    fn part2(input: &Self::Input) -> Result<Answer, String>;
+}
+
+/// A parsed puzzle input with its solution's type erased, so days with
+/// different input types can be driven through the same registry.
+pub trait Parsed {
+    fn part1(&self) -> Result<Answer, String>;
+    fn part2(&self) -> Result<Answer, String>;
+}
+
+struct Prepared<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Prepared<S> {
+    fn part1(&self) -> Result<Answer, String> {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> Result<Answer, String> {
+        S::part2(&self.0)
+    }
+}
+
+fn prepare<S: Solution + 'static>(data: &str) -> Result<Box<dyn Parsed>, ParseError> {
+    Ok(Box::new(Prepared::<S>(S::parse(data)?)))
+}
+
+pub struct Day {
+    pub number: u32,
+    pub example: &'static str,
+    pub parse: fn(&str) -> Result<Box<dyn Parsed>, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S: Solution + 'static>(number: u32) -> Self {
+        Day {
+            number,
+            example: S::EXAMPLE,
+            parse: prepare::<S>,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = Vec<String>;
+
+        const EXAMPLE: &'static str = "ab\ncde";
+
+        fn parse(data: &str) -> Result<Self::Input, ParseError> {
+            Ok(data.lines().map(String::from).collect())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, String> {
+            Ok(input.len().into())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer, String> {
+            input
+                .iter()
+                .max_by_key(|line| line.len())
+                .map(|line| line.as_str().into())
+                .ok_or("no lines".to_string())
+        }
+    }
+
+    #[test]
+    fn day_parses_once_for_both_parts() {
+        let day = Day::new::<Lines>(1);
+        let parsed = (day.parse)(day.example).unwrap();
+        assert_eq!(day.number, 1);
+        assert_eq!(parsed.part1().unwrap(), 2);
+        assert_eq!(parsed.part2().unwrap(), "cde");
+        assert_eq!(
+            (day.parse)("").unwrap().part2(),
+            Err("no lines".to_string())
+        );
+    }
+}
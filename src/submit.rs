@@ -0,0 +1,460 @@
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
+
+use crate::answer::Answer;
+use crate::answers::{format_value, parse_value};
+use crate::client::Client;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    TooHigh,
+    TooLow,
+    Wrong,
+    Wait { seconds: u64 },
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    /// How the outcome is written in a submission log: a keyword, followed
+    /// by the number of seconds for `Wait`.
+    fn log_field(&self) -> String {
+        match self {
+            Outcome::Right => "right".to_string(),
+            Outcome::TooHigh => "too-high".to_string(),
+            Outcome::TooLow => "too-low".to_string(),
+            Outcome::Wrong => "wrong".to_string(),
+            Outcome::Wait { seconds } => format!("wait {}", seconds),
+            Outcome::WrongLevel => "wrong-level".to_string(),
+            Outcome::Unknown => "unknown".to_string(),
+        }
+    }
+
+    /// Parses an outcome written by `log_field` from the start of `text`,
+    /// returning it with the rest of `text`.
+    fn parse_log_field(text: &str) -> Option<(Outcome, &str)> {
+        let (keyword, rest) = text.split_once(' ')?;
+        let outcome = match keyword {
+            "right" => Outcome::Right,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "wait" => {
+                let (seconds, rest) = rest.split_once(' ')?;
+                let seconds = seconds.parse().ok()?;
+                return Some((Outcome::Wait { seconds }, rest));
+            }
+            "wrong-level" => Outcome::WrongLevel,
+            "unknown" => Outcome::Unknown,
+            _ => return None,
+        };
+        Some((outcome, rest))
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "That's the right answer"),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Outcome::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Outcome::Wrong => write!(f, "That's not the right answer"),
+            Outcome::Wait { seconds } => write!(f, "Answered too recently, wait {}s", seconds),
+            Outcome::WrongLevel => write!(f, "That part is locked or already solved"),
+            Outcome::Unknown => write!(f, "Unrecognised response"),
+        }
+    }
+}
+
+/// Extracts the readable text of the `<article>` in a response page.
+pub fn message(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn wait_seconds(message: &str) -> u64 {
+    let Some(start) = message.find("You have ") else {
+        return 0;
+    };
+    let rest = &message[start + "You have ".len()..];
+    let rest = rest.split(" left").next().unwrap_or("");
+
+    rest.split_whitespace()
+        .filter_map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+pub fn parse_outcome(page: &str) -> Outcome {
+    let message = message(page);
+    if message.contains("That's the right answer") {
+        Outcome::Right
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Outcome::TooHigh
+        } else if message.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::Wait {
+            seconds: wait_seconds(&message),
+        }
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u32,
+    pub outcome: Outcome,
+    pub answer: Answer,
+}
+
+/// The submissions for a day, stored next to its cached input as
+/// `data/dayN.submissions`, one `<part> <outcome> <answer>` line each. A
+/// `Wait` outcome is written as `wait <seconds>`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Log {
+    pub submissions: Vec<Submission>,
+}
+
+pub fn log_path(data_dir: &Path, day: u32) -> PathBuf {
+    data_dir.join(format!("day{}.submissions", day))
+}
+
+impl Log {
+    pub fn parse(text: &str) -> Result<Log, String> {
+        let submissions = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                let error = |e: &str| format!("Line {}: {}", number + 1, e);
+                let Some((part, rest)) = line.split_once(' ') else {
+                    return Err(error("expected `<part> <outcome> <answer>`"));
+                };
+                let (outcome, answer) =
+                    Outcome::parse_log_field(rest).ok_or(error("invalid outcome"))?;
+
+                Ok(Submission {
+                    part: part.parse().map_err(|_| error("invalid part"))?,
+                    outcome,
+                    answer: parse_value(answer).map_err(|e| error(&e))?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Log { submissions })
+    }
+
+    /// Loads the submissions for a day, treating a missing log as empty.
+    pub fn load(data_dir: &Path, day: u32) -> Result<Log, String> {
+        let path = log_path(data_dir, day);
+        match fs::read_to_string(&path) {
+            Ok(text) => Log::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Log::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn append(&mut self, data_dir: &Path, day: u32, submission: Submission) -> io::Result<()> {
+        let line = format!(
+            "{} {} {}\n",
+            submission.part,
+            submission.outcome.log_field(),
+            format_value(&submission.answer)
+        );
+
+        fs::create_dir_all(data_dir)?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path(data_dir, day))?;
+        io::Write::write_all(&mut file, line.as_bytes())?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+
+    pub fn solved(&self, part: u32) -> Option<&Answer> {
+        self.submissions
+            .iter()
+            .find(|s| s.part == part && s.outcome == Outcome::Right)
+            .map(|s| &s.answer)
+    }
+}
+
+/// Why a freshly computed answer can't be right, based on earlier rejections.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Warning {
+    Rejected(Answer),
+    TooHigh { bound: Answer },
+    TooLow { bound: Answer },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::Rejected(answer) => write!(f, "{} was already rejected", answer),
+            Warning::TooHigh { bound } => {
+                write!(f, "answer must be below {}, which was too high", bound)
+            }
+            Warning::TooLow { bound } => {
+                write!(f, "answer must be above {}, which was too low", bound)
+            }
+        }
+    }
+}
+
+impl Log {
+    fn rejected(&self, part: u32, outcome: Outcome) -> impl Iterator<Item = &Answer> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.part == part && s.outcome == outcome)
+            .map(|s| &s.answer)
+    }
+
+    /// The tightest known bounds on a part's answer: the largest guess that
+    /// was too low and the smallest that was too high.
+    pub fn bounds(&self, part: u32) -> (Option<&Answer>, Option<&Answer>) {
+        let pick = |outcome, better: fn(&Answer, &Answer) -> bool| {
+            self.rejected(part, outcome)
+                .fold(None, |best: Option<&Answer>, answer| match best {
+                    Some(b) if !better(answer, b) => Some(b),
+                    _ => Some(answer),
+                })
+        };
+
+        (
+            pick(Outcome::TooLow, |a, b| a > b),
+            pick(Outcome::TooHigh, |a, b| a < b),
+        )
+    }
+
+    /// Checks an answer against earlier rejections of the same part.
+    pub fn check(&self, part: u32, answer: &Answer) -> Option<Warning> {
+        let rejected = [Outcome::TooHigh, Outcome::TooLow, Outcome::Wrong]
+            .into_iter()
+            .any(|outcome| self.rejected(part, outcome).any(|a| a == answer));
+        if rejected {
+            return Some(Warning::Rejected(answer.clone()));
+        }
+
+        let (low, high) = self.bounds(part);
+        if let Some(low) = low
+            && answer <= low
+        {
+            return Some(Warning::TooLow { bound: low.clone() });
+        }
+        if let Some(high) = high
+            && answer >= high
+        {
+            return Some(Warning::TooHigh {
+                bound: high.clone(),
+            });
+        }
+
+        None
+    }
+}
+
+impl Client {
+    pub fn submit(&self, day: u32, part: u32, answer: &Answer) -> Result<Outcome, String> {
+        let response = self.request(
+            &format!("/day/{}/answer", day),
+            &[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ],
+        )?;
+        if response.status != 200 {
+            return Err(format!(
+                "Failed to submit day {} part {}: HTTP {}: {}",
+                day,
+                part,
+                response.status,
+                message(&response.body)
+            ));
+        }
+
+        Ok(parse_outcome(&response.body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub;
+
+    fn page(text: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main><article><p>{}</p></article></main></body></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn parse_outcome_works() {
+        let right = page("That's the right answer! You are <em>one gold star</em> closer.");
+        assert_eq!(parse_outcome(&right), Outcome::Right);
+
+        let high =
+            page("That's not the right answer; your answer is too high. Please wait one minute.");
+        assert_eq!(parse_outcome(&high), Outcome::TooHigh);
+
+        let low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_outcome(&low), Outcome::TooLow);
+
+        let wrong = page(
+            "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+        );
+        assert_eq!(parse_outcome(&wrong), Outcome::Wrong);
+
+        let wait = page(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.",
+        );
+        assert_eq!(parse_outcome(&wait), Outcome::Wait { seconds: 65 });
+
+        let level =
+            page("You don't seem to be solving the right level. Did you already complete it?");
+        assert_eq!(parse_outcome(&level), Outcome::WrongLevel);
+
+        assert_eq!(parse_outcome("<html></html>"), Outcome::Unknown);
+    }
+
+    #[test]
+    fn log_round_trip() {
+        let data = std::env::temp_dir().join(format!("aoc2025-log-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&data);
+        let mut log = Log::default();
+        log.append(
+            &data,
+            4,
+            Submission {
+                part: 1,
+                outcome: Outcome::TooLow,
+                answer: Answer::from(12),
+            },
+        )
+        .unwrap();
+        log.append(
+            &data,
+            4,
+            Submission {
+                part: 2,
+                outcome: Outcome::Right,
+                answer: Answer::from("ABC DEF"),
+            },
+        )
+        .unwrap();
+        log.append(
+            &data,
+            4,
+            Submission {
+                part: 1,
+                outcome: Outcome::Wait { seconds: 65 },
+                answer: Answer::from(30),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(log_path(&data, 4))
+                .unwrap()
+                .lines()
+                .last(),
+            Some("1 wait 65 30")
+        );
+        assert_eq!(Log::load(&data, 4).unwrap(), log);
+        assert_eq!(log.solved(2), Some(&Answer::from("ABC DEF")));
+        assert_eq!(log.solved(1), None);
+        fs::remove_dir_all(&data).unwrap();
+    }
+
+    #[test]
+    fn log_bounds_and_checks() {
+        let log = Log::parse(
+            "1 too-low 10\n1 too-high 100\n1 too-low 20\n1 too-high 90\n1 wrong 50\n2 too-high 5\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            log.bounds(1),
+            (Some(&Answer::from(20)), Some(&Answer::from(90)))
+        );
+        assert_eq!(log.bounds(2), (None, Some(&Answer::from(5))));
+
+        assert_eq!(log.check(1, &Answer::from(30)), None);
+        assert_eq!(
+            log.check(1, &Answer::from(50usize)),
+            Some(Warning::Rejected(Answer::from(50)))
+        );
+        assert_eq!(
+            log.check(1, &Answer::from(15)),
+            Some(Warning::TooLow {
+                bound: Answer::from(20)
+            })
+        );
+        assert_eq!(
+            log.check(1, &Answer::from(90)),
+            Some(Warning::Rejected(Answer::from(90)))
+        );
+        assert_eq!(
+            log.check(1, &Answer::from(95)),
+            Some(Warning::TooHigh {
+                bound: Answer::from(90)
+            })
+        );
+        assert_eq!(log.check(2, &Answer::from(4)), None);
+    }
+
+    #[test]
+    fn submit_posts_answer() {
+        if stub::curl_missing() {
+            return;
+        }
+        let (url, server) = stub::serve(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = Client::new(&url, "secret").unwrap();
+
+        assert_eq!(
+            client.submit(3, 2, &Answer::from(357)).unwrap(),
+            Outcome::Right
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].head.starts_with("POST /day/3/answer "));
+        assert_eq!(requests[0].body, "level=2&answer=357");
+    }
+}